@@ -0,0 +1,172 @@
+use std::{env, fs, ops::RangeInclusive, process::ExitCode, time::Instant};
+
+use common::{ParseError, Solution};
+
+const USAGE: &str = "usage: aoc run [--day <1-14>] [--part <1|2>] [--input <path>]";
+
+const DAYS: u32 = 14;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args = match parse_args(&args) {
+        Ok(args) => args,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct RunArgs {
+    day: Option<u32>,
+    part: Option<u32>,
+    input: Option<String>,
+}
+
+fn parse_args(args: &[String]) -> Result<RunArgs, String> {
+    let mut iter = args.iter();
+    match iter.next().map(String::as_str) {
+        Some("run") => (),
+        Some(cmd) => return Err(format!("unknown command: {}", cmd)),
+        None => return Err("missing command".to_string()),
+    }
+    let mut run_args = RunArgs {
+        day: None,
+        part: None,
+        input: None,
+    };
+    while let Some(flag) = iter.next() {
+        let value = iter
+            .next()
+            .ok_or_else(|| format!("missing value for {}", flag))?;
+        match flag.as_str() {
+            "--day" => run_args.day = Some(parse_number(flag, value, 1..=DAYS)?),
+            "--part" => run_args.part = Some(parse_number(flag, value, 1..=2)?),
+            "--input" => run_args.input = Some(value.clone()),
+            _ => return Err(format!("unknown flag: {}", flag)),
+        }
+    }
+    if run_args.input.is_some() && run_args.day.is_none() {
+        return Err("--input requires --day".to_string());
+    }
+    Ok(run_args)
+}
+
+fn parse_number(flag: &str, value: &str, range: RangeInclusive<u32>) -> Result<u32, String> {
+    value
+        .parse()
+        .ok()
+        .filter(|n| range.contains(n))
+        .ok_or_else(|| {
+            format!(
+                "{} must be between {} and {}, got {}",
+                flag,
+                range.start(),
+                range.end(),
+                value
+            )
+        })
+}
+
+fn run(args: &RunArgs) -> Result<(), String> {
+    let days = match args.day {
+        Some(day) => day..=day,
+        None => 1..=DAYS,
+    };
+    let parts = match args.part {
+        Some(part) => part..=part,
+        None => 1..=2,
+    };
+    for day in days {
+        let path = match &args.input {
+            Some(path) => path.clone(),
+            None => format!("day{:02}/input.txt", day),
+        };
+        let input = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path, e))?;
+        let result = match day {
+            1 => run_day(day, &day01::Day01, parts.clone(), &input),
+            2 => run_day(day, &day02::Day02, parts.clone(), &input),
+            3 => run_day(day, &day03::Day03, parts.clone(), &input),
+            4 => run_day(day, &day04::Day04, parts.clone(), &input),
+            5 => run_day(day, &day05::Day05, parts.clone(), &input),
+            6 => run_day(day, &day06::Day06, parts.clone(), &input),
+            7 => run_day(day, &day07::Day07, parts.clone(), &input),
+            8 => run_day(day, &day08::Day08, parts.clone(), &input),
+            9 => run_day(day, &day09::Day09, parts.clone(), &input),
+            10 => run_day(day, &day10::Day10, parts.clone(), &input),
+            11 => run_day(day, &day11::Day11, parts.clone(), &input),
+            12 => run_day(day, &day12::Day12, parts.clone(), &input),
+            13 => run_day(day, &day13::Day13, parts.clone(), &input),
+            14 => run_day(day, &day14::Day14::default(), parts.clone(), &input),
+            _ => unreachable!("day is validated by parse_args"),
+        };
+        result.map_err(|e| format!("{}: {}", path, e))?;
+    }
+    Ok(())
+}
+
+fn run_day<S: Solution>(
+    day: u32,
+    solution: &S,
+    parts: RangeInclusive<u32>,
+    input: &str,
+) -> Result<(), ParseError> {
+    let start = Instant::now();
+    let parsed = solution.parse(input)?;
+    println!("day {:02} parse: ({:.2?})", day, start.elapsed());
+    for part in parts {
+        let start = Instant::now();
+        let answer = match part {
+            1 => solution.part1(&parsed),
+            _ => solution.part2(&parsed),
+        };
+        println!(
+            "day {:02} part {}: {} ({:.2?})",
+            day,
+            part,
+            answer,
+            start.elapsed()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let result = parse_args(&args("run --day 6 --part 2 --input foo.txt"));
+        assert_eq!(
+            result,
+            Ok(RunArgs {
+                day: Some(6),
+                part: Some(2),
+                input: Some("foo.txt".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_args_bad() {
+        assert!(parse_args(&args("run --day 15")).is_err());
+        assert!(parse_args(&args("run --part 3")).is_err());
+        assert!(parse_args(&args("run --input foo.txt")).is_err());
+        assert!(parse_args(&args("walk --day 1")).is_err());
+    }
+}
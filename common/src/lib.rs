@@ -0,0 +1,109 @@
+use std::{error::Error, fmt, fs, io, process};
+
+mod parse;
+
+pub use parse::{char_map, lines, Cursor, ParseError};
+
+// Every day solves its puzzle in two steps: parse the text once into a typed
+// model, then answer each part from that model. Keeping the steps apart lets
+// the runner time them separately and lets other tools reuse the parsed input.
+pub trait Solution {
+    type Input;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(&self, input: &Self::Input) -> Answer;
+
+    fn part2(&self, input: &Self::Input) -> Answer;
+}
+
+// Reads and parses a day's input file, exiting with a diagnostic if either fails.
+pub fn parse_file<S: Solution>(solution: &S, path: &str) -> S::Input {
+    let parsed = fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|input| solution.parse(&input).map_err(|e| e.to_string()));
+    match parsed {
+        Ok(input) => input,
+        Err(msg) => {
+            eprintln!("{}: {}", path, msg);
+            process::exit(1);
+        }
+    }
+}
+
+// Failure while streaming input from a reader rather than a parsed string.
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Io(e) => write!(f, "{}", e),
+            ReadError::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for ReadError {}
+
+impl From<io::Error> for ReadError {
+    fn from(e: io::Error) -> Self {
+        ReadError::Io(e)
+    }
+}
+
+impl From<ParseError> for ReadError {
+    fn from(e: ParseError) -> Self {
+        ReadError::Parse(e)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    UInt(u64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::UInt(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Answer::Int(n.into())
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Int(n)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Answer::UInt(n)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::UInt(n as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
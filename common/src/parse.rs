@@ -0,0 +1,248 @@
+use std::{any::type_name, error::Error, fmt, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    // line and column are 1-based, columns count chars rather than bytes
+    pub line: usize,
+    pub column: usize,
+    // the offending text, empty when the line ended early
+    pub found: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, found: &str, expected: &str) -> ParseError {
+        ParseError {
+            line,
+            column,
+            found: found.to_string(),
+            expected: expected.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        if self.found.is_empty() {
+            write!(f, "end of line")
+        } else {
+            write!(f, "{:?}", self.found)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+// Walks a single line of input, keeping track of where it is so every
+// failure can point at the exact column that didn't match.
+pub struct Cursor<'a> {
+    line_no: usize,
+    line: &'a str,
+    pos: usize,
+}
+
+pub fn lines(input: &str) -> impl Iterator<Item = Cursor<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| Cursor::new(i + 1, line))
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(line_no: usize, line: &'a str) -> Cursor<'a> {
+        Cursor {
+            line_no,
+            line,
+            pos: 0,
+        }
+    }
+
+    pub fn line_no(&self) -> usize {
+        self.line_no
+    }
+
+    pub fn column(&self) -> usize {
+        self.line[..self.pos].chars().count() + 1
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.line[self.pos..]
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.pos == self.line.len()
+    }
+
+    // The error for whatever token sits at the cursor.
+    pub fn error(&self, expected: &str) -> ParseError {
+        let found = self.rest().split_whitespace().next().unwrap_or("");
+        ParseError::new(self.line_no, self.column(), found, expected)
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    pub fn next_char(&mut self) -> Option<char> {
+        let c = self.rest().chars().next()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    pub fn expect(&mut self, literal: &str) -> Result<(), ParseError> {
+        if self.rest().starts_with(literal) {
+            self.pos += literal.len();
+            Ok(())
+        } else {
+            Err(self.error(&format!("{:?}", literal)))
+        }
+    }
+
+    pub fn digit(&mut self) -> Result<u32, ParseError> {
+        match self.rest().chars().next().and_then(|c| c.to_digit(10)) {
+            Some(digit) => {
+                self.pos += 1;
+                Ok(digit)
+            }
+            None => Err(self.error("digit")),
+        }
+    }
+
+    // An optionally negative run of digits, parsed as `T`.
+    pub fn number<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let rest = self.rest();
+        let sign = usize::from(rest.starts_with('-'));
+        let digits = rest[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len() - sign);
+        if digits == 0 {
+            return Err(self.error("number"));
+        }
+        let text = &rest[..sign + digits];
+        let num = text.parse().map_err(|_| {
+            ParseError::new(
+                self.line_no,
+                self.column(),
+                text,
+                &format!("number that fits in {}", type_name::<T>()),
+            )
+        })?;
+        self.pos += text.len();
+        Ok(num)
+    }
+
+    pub fn end(&self) -> Result<(), ParseError> {
+        if self.at_end() {
+            Ok(())
+        } else {
+            Err(self.error("end of line"))
+        }
+    }
+}
+
+// Parses a rectangular map of characters, one row per line, mapping each
+// character through `f`. `expected` describes the characters `f` accepts.
+pub fn char_map<T>(
+    input: &str,
+    expected: &str,
+    f: impl Fn(char) -> Option<T>,
+) -> Result<Vec<Vec<T>>, ParseError> {
+    let mut rows: Vec<Vec<T>> = Vec::new();
+    for mut cursor in lines(input) {
+        let mut row = Vec::new();
+        while let Some(c) = cursor.next_char() {
+            match f(c) {
+                Some(val) => row.push(val),
+                None => {
+                    let column = cursor.column() - 1;
+                    return Err(ParseError::new(
+                        cursor.line_no(),
+                        column,
+                        &c.to_string(),
+                        expected,
+                    ));
+                }
+            }
+        }
+        if let Some(first) = rows.first() {
+            if row.len() != first.len() {
+                let column = first.len().min(row.len()) + 1;
+                let found: String = cursor.line.chars().skip(column - 1).collect();
+                let expected = if row.len() < first.len() {
+                    expected.to_string()
+                } else {
+                    "end of line".to_string()
+                };
+                return Err(ParseError::new(cursor.line_no(), column, &found, &expected));
+            }
+        }
+        rows.push(row);
+    }
+    if rows.first().is_none_or(|row| row.is_empty()) {
+        return Err(ParseError::new(1, 1, "", expected));
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        let mut cursor = Cursor::new(1, "12 -4");
+        assert_eq!(cursor.number::<i32>(), Ok(12));
+        cursor.skip_whitespace();
+        assert_eq!(cursor.number::<i32>(), Ok(-4));
+        assert_eq!(cursor.end(), Ok(()));
+    }
+
+    #[test]
+    fn test_number_error() {
+        let mut cursor = Cursor::new(3, "12 x4");
+        cursor.number::<i32>().unwrap();
+        cursor.skip_whitespace();
+        let err = cursor.number::<i32>().unwrap_err();
+        assert_eq!(err, ParseError::new(3, 4, "x4", "number"));
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 4: expected number, found \"x4\""
+        );
+    }
+
+    #[test]
+    fn test_number_overflow() {
+        let mut cursor = Cursor::new(1, "300");
+        let err = cursor.number::<u8>().unwrap_err();
+        assert_eq!(err.expected, "number that fits in u8");
+    }
+
+    #[test]
+    fn test_truncated() {
+        let mut cursor = Cursor::new(2, "p=1,");
+        cursor.expect("p=").unwrap();
+        cursor.number::<i32>().unwrap();
+        cursor.expect(",").unwrap();
+        let err = cursor.number::<i32>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 5: expected number, found end of line"
+        );
+    }
+
+    #[test]
+    fn test_char_map_ragged() {
+        let err = char_map("..\n.", "'.'", |c| (c == '.').then_some(())).unwrap_err();
+        assert_eq!(err, ParseError::new(2, 2, "", "'.'"));
+
+        let err = char_map("..\n.x", "'.'", |c| (c == '.').then_some(())).unwrap_err();
+        assert_eq!(err, ParseError::new(2, 2, "x", "'.'"));
+    }
+}
@@ -0,0 +1,233 @@
+use std::{collections::HashMap, fmt, hash::Hash, str::FromStr};
+
+use common::{lines, ParseError};
+
+// Anything the lists can hold. Scores are worked out in i128 so sums of
+// i64 or u64 gaps don't overflow, though their squares can.
+pub trait Number: Copy + Ord + Hash + FromStr + Into<i128> {}
+
+impl<T: Copy + Ord + Hash + FromStr + Into<i128>> Number for T {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Score {
+    Int(i128),
+    Real(f64),
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Score::Int(n) => write!(f, "{}", n),
+            Score::Real(x) => write!(f, "{:.6}", x),
+        }
+    }
+}
+
+pub trait Metric<T> {
+    fn score(&self, first: &[T], second: &[T]) -> Score;
+}
+
+// Sort both lists and add up how far apart each pair is (part 1).
+pub struct SortedDistance;
+
+// Like SortedDistance, but squaring each gap. The square of a gap near the
+// top of the u64 range doesn't fit in i128, so a sum that overflows is
+// given as a Real instead.
+pub struct SquaredDistance;
+
+// Each number in the first list times how often it is in the second (part 2).
+pub struct Similarity;
+
+// Spearman's rank correlation of the two lists, paired row by row. Tied
+// values share the average of their ranks.
+pub struct RankCorrelation;
+
+fn sorted_gaps<T: Number>(first: &[T], second: &[T]) -> impl Iterator<Item = i128> {
+    let mut first = first.to_vec();
+    let mut second = second.to_vec();
+    first.sort_unstable();
+    second.sort_unstable();
+    first
+        .into_iter()
+        .zip(second)
+        .map(|(f, s)| (f.into() - s.into()).abs())
+}
+
+impl<T: Number> Metric<T> for SortedDistance {
+    fn score(&self, first: &[T], second: &[T]) -> Score {
+        Score::Int(sorted_gaps(first, second).sum())
+    }
+}
+
+impl<T: Number> Metric<T> for SquaredDistance {
+    fn score(&self, first: &[T], second: &[T]) -> Score {
+        let exact = sorted_gaps(first, second)
+            .try_fold(0i128, |sum, gap| sum.checked_add(gap.checked_mul(gap)?));
+        match exact {
+            Some(sum) => Score::Int(sum),
+            None => Score::Real(
+                sorted_gaps(first, second)
+                    .map(|gap| (gap as f64) * (gap as f64))
+                    .sum(),
+            ),
+        }
+    }
+}
+
+impl<T: Number> Metric<T> for Similarity {
+    fn score(&self, first: &[T], second: &[T]) -> Score {
+        let mut counts: HashMap<T, i128> = HashMap::new();
+        for n in second {
+            *counts.entry(*n).or_insert(0) += 1;
+        }
+        let sum = first
+            .iter()
+            .map(|n| counts.get(n).unwrap_or(&0) * (*n).into())
+            .sum();
+        Score::Int(sum)
+    }
+}
+
+impl<T: Number> Metric<T> for RankCorrelation {
+    fn score(&self, first: &[T], second: &[T]) -> Score {
+        let len = first.len().min(second.len());
+        let (a, b) = (ranks(&first[..len]), ranks(&second[..len]));
+        let mean = (len as f64 + 1.0) / 2.0;
+        let mut cov = 0.0;
+        let mut var_a = 0.0;
+        let mut var_b = 0.0;
+        for (ra, rb) in a.iter().zip(b.iter()) {
+            cov += (ra - mean) * (rb - mean);
+            var_a += (ra - mean) * (ra - mean);
+            var_b += (rb - mean) * (rb - mean);
+        }
+        // NaN when either list is constant, as there is nothing to rank
+        Score::Real(cov / (var_a * var_b).sqrt())
+    }
+}
+
+// 1-based ranks, ties getting the average of the ranks they span
+fn ranks<T: Number>(list: &[T]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..list.len()).collect();
+    order.sort_by_key(|&i| list[i]);
+    let mut ranks = vec![0.0; list.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && list[order[end]] == list[order[start]] {
+            end += 1;
+        }
+        let rank = (start + end + 1) as f64 / 2.0;
+        for &i in &order[start..end] {
+            ranks[i] = rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pairs {
+    One(usize, usize),
+    All,
+}
+
+// Whitespace separated columns of numbers, every line the same width.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Table<T> {
+    pub columns: Vec<Vec<T>>,
+}
+
+impl<T: Number> Table<T> {
+    pub fn parse(input: &str) -> Result<Table<T>, ParseError> {
+        let mut columns: Vec<Vec<T>> = Vec::new();
+        for mut line in lines(input) {
+            line.skip_whitespace();
+            let mut row = Vec::new();
+            // the first line decides how many columns there are
+            while (columns.is_empty() && !line.at_end()) || row.len() < columns.len() {
+                row.push(line.number()?);
+                line.skip_whitespace();
+            }
+            line.end()?;
+            if columns.is_empty() {
+                columns = row.into_iter().map(|n| vec![n]).collect();
+            } else {
+                for (column, n) in columns.iter_mut().zip(row) {
+                    column.push(n);
+                }
+            }
+        }
+        if columns.is_empty() {
+            return Err(ParseError::new(1, 1, "", "number"));
+        }
+        Ok(Table { columns })
+    }
+
+    // Scores the chosen pair of columns, or every pair (i < j) in order.
+    // Panics if a column is out of range.
+    pub fn compare(&self, pairs: Pairs, metric: &dyn Metric<T>) -> Vec<((usize, usize), Score)> {
+        let pairs = match pairs {
+            Pairs::One(i, j) => vec![(i, j)],
+            Pairs::All => (0..self.columns.len())
+                .flat_map(|i| (i + 1..self.columns.len()).map(move |j| (i, j)))
+                .collect(),
+        };
+        pairs
+            .into_iter()
+            .map(|(i, j)| ((i, j), metric.score(&self.columns[i], &self.columns[j])))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_all_pairs() {
+        let table: Table<u64> = Table::parse("1 2 3\n2 4 1\n3 6 2").unwrap();
+        let scores = table.compare(Pairs::All, &SquaredDistance);
+        assert_eq!(
+            scores,
+            vec![
+                ((0, 1), Score::Int(1 + 4 + 9)),
+                ((0, 2), Score::Int(0)),
+                ((1, 2), Score::Int(1 + 4 + 9)),
+            ]
+        );
+        assert_eq!(
+            table.compare(Pairs::One(0, 1), &RankCorrelation),
+            vec![((0, 1), Score::Real(1.0))]
+        );
+    }
+
+    #[test]
+    fn test_rank_ties() {
+        assert_eq!(ranks(&[10, 20, 10, 5]), vec![2.5, 4.0, 2.5, 1.0]);
+        let score = RankCorrelation.score(&[1i64, 2, 3, 4], &[40i64, 30, 20, 10]);
+        assert_eq!(score, Score::Real(-1.0));
+    }
+
+    #[test]
+    fn test_squared_overflow() {
+        let score = SquaredDistance.score(&[u64::MAX], &[0]);
+        assert_eq!(score, Score::Real((u64::MAX as f64) * (u64::MAX as f64)));
+        let score = SquaredDistance.score(&[u64::MAX], &[u64::MAX - 3]);
+        assert_eq!(score, Score::Int(9));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let result = Table::<i64>::parse("1 2 3\n4 5").err();
+        assert_eq!(result, Some(ParseError::new(2, 4, "", "number")));
+        let result = Table::<i64>::parse("1 2\n4 5 6").err();
+        assert_eq!(result, Some(ParseError::new(2, 5, "6", "end of line")));
+        let result = Table::<u64>::parse("1 -2").err();
+        assert_eq!(
+            result,
+            Some(ParseError::new(1, 3, "-2", "number that fits in u64"))
+        );
+    }
+}
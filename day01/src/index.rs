@@ -0,0 +1,189 @@
+use crate::Number;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    First,
+    Second,
+}
+
+// Keeps both scores up to date while numbers are added to and removed
+// from either list.
+//
+// Each list is kept sorted, so a number's rank is a binary search away.
+// The similarity only ever changes by the one value being touched, so it
+// is updated from the count of that value in the other list in O(log n).
+// The distance is not that cheap: one insert or remove shifts the partner
+// of every larger number, and no structure here gets around re-scoring
+// those pairs, so an update costs O(n) in the worst case (a new smallest
+// number) and O(1) for a new largest one. The pairs before the change are
+// never looked at.
+pub struct ListIndex<T> {
+    first: Vec<T>,
+    second: Vec<T>,
+    similarity: i128,
+    distance: i128,
+}
+
+impl<T: Number> Default for ListIndex<T> {
+    fn default() -> Self {
+        ListIndex::new()
+    }
+}
+
+impl<T: Number> ListIndex<T> {
+    pub fn new() -> ListIndex<T> {
+        ListIndex {
+            first: Vec::new(),
+            second: Vec::new(),
+            similarity: 0,
+            distance: 0,
+        }
+    }
+
+    // Sorts each list once and scores them in one pass.
+    pub fn from_lists(first: &[T], second: &[T]) -> ListIndex<T> {
+        let mut first = first.to_vec();
+        let mut second = second.to_vec();
+        first.sort_unstable();
+        second.sort_unstable();
+        let distance = first
+            .iter()
+            .zip(second.iter())
+            .map(|(a, b)| ((*a).into() - (*b).into()).abs())
+            .sum();
+        // walk both lists a run of equal values at a time
+        let mut similarity = 0;
+        let (mut i, mut j) = (0, 0);
+        while i < first.len() && j < second.len() {
+            let n = first[i].min(second[j]);
+            let run_a = first[i..].partition_point(|m| *m == n);
+            let run_b = second[j..].partition_point(|m| *m == n);
+            similarity += (run_a * run_b) as i128 * n.into();
+            i += run_a;
+            j += run_b;
+        }
+        ListIndex {
+            first,
+            second,
+            similarity,
+            distance,
+        }
+    }
+
+    pub fn insert(&mut self, side: Side, n: T) {
+        let (list, other) = self.lists(side);
+        let rank = list.partition_point(|m| *m < n);
+        // pair i from `rank` on goes from (list[i], other[i]) to
+        // (list[i - 1], other[i]), with `n` taking pair `rank`
+        let mut change = 0;
+        let mut partner = n;
+        for (i, b) in other.iter().enumerate().skip(rank) {
+            let b = (*b).into();
+            change += (partner.into() - b).abs();
+            let Some(a) = list.get(i) else {
+                break;
+            };
+            change -= ((*a).into() - b).abs();
+            partner = *a;
+        }
+        list.insert(rank, n);
+        let matches = count(other, n) as i128;
+        self.similarity += matches * n.into();
+        self.distance += change;
+    }
+
+    // Returns false, changing nothing, if `n` isn't in that list.
+    pub fn remove(&mut self, side: Side, n: T) -> bool {
+        let (list, other) = self.lists(side);
+        let rank = list.partition_point(|m| *m < n);
+        if list.get(rank) != Some(&n) {
+            return false;
+        }
+        // pair i from `rank` on goes from (list[i], other[i]) to
+        // (list[i + 1], other[i])
+        let mut change = 0;
+        for (i, b) in other.iter().enumerate().skip(rank) {
+            let b = (*b).into();
+            let Some(a) = list.get(i) else {
+                break;
+            };
+            change -= ((*a).into() - b).abs();
+            if let Some(next) = list.get(i + 1) {
+                change += ((*next).into() - b).abs();
+            }
+        }
+        list.remove(rank);
+        let matches = count(other, n) as i128;
+        self.similarity -= matches * n.into();
+        self.distance += change;
+        true
+    }
+
+    pub fn similarity(&self) -> i128 {
+        self.similarity
+    }
+
+    // The sorted pairing distance. If one list is longer, its largest
+    // numbers have no partner and are left out.
+    pub fn distance(&self) -> i128 {
+        self.distance
+    }
+
+    fn lists(&mut self, side: Side) -> (&mut Vec<T>, &[T]) {
+        match side {
+            Side::First => (&mut self.first, &self.second),
+            Side::Second => (&mut self.second, &self.first),
+        }
+    }
+}
+
+// how many times `n` is in a sorted list
+fn count<T: Number>(list: &[T], n: T) -> usize {
+    list.partition_point(|m| *m <= n) - list.partition_point(|m| *m < n)
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::{Metric, Score, Similarity, SortedDistance};
+
+    #[test]
+    fn test_updates() {
+        let mut first = vec![3, 4, 2, 1, 3, 3];
+        let mut second = vec![4, 3, 5, 3, 9, 3];
+        let mut index = ListIndex::from_lists(&first, &second);
+        assert_eq!(index.distance(), 11);
+        assert_eq!(index.similarity(), 31);
+        let mut built = ListIndex::new();
+        for n in &first {
+            built.insert(Side::First, *n);
+        }
+        for n in &second {
+            built.insert(Side::Second, *n);
+        }
+        assert_eq!((built.distance(), built.similarity()), (11, 31));
+
+        index.insert(Side::First, 9);
+        index.insert(Side::Second, 1);
+        assert!(index.remove(Side::Second, 3));
+        assert!(index.remove(Side::First, 3));
+        assert!(!index.remove(Side::First, 7));
+        first.extend([9]);
+        first.remove(0);
+        second.extend([1]);
+        second.remove(1);
+
+        let expected = SortedDistance.score(&first, &second);
+        assert_eq!(Score::Int(index.distance()), expected);
+        let expected = Similarity.score(&first, &second);
+        assert_eq!(Score::Int(index.similarity()), expected);
+
+        // the largest numbers of the longer list have no partner
+        index.insert(Side::Second, 0);
+        index.insert(Side::Second, 2);
+        second.extend([0, 2]);
+        let expected = SortedDistance.score(&first, &second);
+        assert_eq!(Score::Int(index.distance()), expected);
+    }
+}
@@ -0,0 +1,167 @@
+use std::io::BufRead;
+
+use common::{lines, Answer, Cursor, ParseError, ReadError, Solution};
+
+mod compare;
+mod index;
+pub use compare::{
+    Metric, Number, Pairs, RankCorrelation, Score, Similarity, SortedDistance, SquaredDistance,
+    Table,
+};
+pub use index::{ListIndex, Side};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Lists;
+
+    fn parse(&self, input: &str) -> Result<Lists, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, lists: &Lists) -> Answer {
+        difference_score(lists).into()
+    }
+
+    fn part2(&self, lists: &Lists) -> Answer {
+        similarity_score(lists).into()
+    }
+}
+
+pub struct Lists {
+    pub first: Vec<i32>,
+    pub second: Vec<i32>,
+}
+
+fn parse(input: &str) -> Result<Lists, ParseError> {
+    let mut first = vec![];
+    let mut second = vec![];
+
+    for mut line in lines(input) {
+        let (f, s) = parse_pair(&mut line)?;
+        first.push(f);
+        second.push(s);
+    }
+
+    Ok(Lists { first, second })
+}
+
+fn parse_pair(line: &mut Cursor) -> Result<(i32, i32), ParseError> {
+    line.skip_whitespace();
+    let first = line.number()?;
+    line.skip_whitespace();
+    let second = line.number()?;
+    line.skip_whitespace();
+    line.end()?;
+    Ok((first, second))
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Scores {
+    pub difference: Score,
+    pub similarity: Score,
+}
+
+// Reads the lists a line at a time into one reused buffer, so the text is
+// never held in memory. Only the two columns themselves are kept, since
+// sorting needs all of them, and are scored like any other pair of columns.
+pub fn scores_from_reader(mut reader: impl BufRead) -> Result<Scores, ReadError> {
+    let mut first = vec![];
+    let mut second = vec![];
+    let mut buf = String::new();
+    let mut line_no = 0;
+
+    while reader.read_line(&mut buf)? > 0 {
+        line_no += 1;
+        let mut line = Cursor::new(line_no, buf.trim_end_matches(['\n', '\r']));
+        let (f, s) = parse_pair(&mut line)?;
+        first.push(f);
+        second.push(s);
+        buf.clear();
+    }
+
+    Ok(Scores {
+        difference: SortedDistance.score(&first, &second),
+        similarity: Similarity.score(&first, &second),
+    })
+}
+
+fn difference_score(lists: &Lists) -> Score {
+    SortedDistance.score(&lists.first, &lists.second)
+}
+
+fn similarity_score(lists: &Lists) -> Score {
+    Similarity.score(&lists.first, &lists.second)
+}
+
+impl From<Score> for Answer {
+    fn from(score: Score) -> Self {
+        match score {
+            Score::Int(n) => match i64::try_from(n) {
+                Ok(n) => Answer::Int(n),
+                Err(_) => Answer::Text(n.to_string()),
+            },
+            Score::Real(_) => Answer::Text(score.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3";
+        let result = difference_score(&parse(input).unwrap());
+        assert_eq!(result, Score::Int(11));
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3";
+        let result = similarity_score(&parse(input).unwrap());
+        assert_eq!(result, Score::Int(31));
+    }
+
+    #[test]
+    fn test_reader() {
+        let input = "3   4\n4   3\n2   5\r\n1   3\n3   9\n3   3\n";
+        let scores = scores_from_reader(input.as_bytes()).unwrap();
+        assert_eq!(
+            scores,
+            Scores {
+                difference: Score::Int(11),
+                similarity: Score::Int(31)
+            }
+        );
+
+        let input = "2000000000 2000000000\n".repeat(3);
+        let scores = scores_from_reader(input.as_bytes()).unwrap();
+        assert_eq!(scores.similarity, Score::Int(18_000_000_000));
+
+        let result = scores_from_reader("3   4\n4   x\n".as_bytes());
+        assert!(
+            matches!(result, Err(ReadError::Parse(e)) if e == ParseError::new(2, 5, "x", "number"))
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "3   4
+4   x";
+        let result = parse(input).err();
+        assert_eq!(result, Some(ParseError::new(2, 5, "x", "number")));
+    }
+}
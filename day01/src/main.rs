@@ -1,95 +1,140 @@
-use std::{collections::HashMap, fs};
-
-fn main() {
-    let input = fs::read_to_string("input.txt").unwrap();
-
-    let diff = difference_score(&input);
-    println!("difference: {}", diff);
-
-    let sim = similarity_score(&input);
-    println!("similarity: {}", sim);
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, BufReader, Read},
+    process,
+};
+
+use day01::{
+    scores_from_reader, Metric, Number, Pairs, RankCorrelation, Similarity, SortedDistance,
+    SquaredDistance, Table,
+};
+
+const USAGE: &str = "usage: day01 [path|-] [--type i64|u64] [--pair A,B | --all-pairs] \
+                     [--metric distance|squared|similarity|rank]";
+
+struct Options {
+    path: String,
+    // None keeps the puzzle's two columns and answers
+    table: Option<TableOptions>,
 }
 
-fn difference_score(input: &str) -> i32 {
-    let mut first = vec![];
-    let mut second = vec![];
-
-    let parsed = parse_iter(input);
-
-    for mut line in parsed {
-        first.push(line.next().unwrap());
-        second.push(line.next().unwrap());
-    }
-
-    first.sort_unstable();
-    second.sort_unstable();
-
-    let sum: i32 = first
-        .iter()
-        .zip(second.iter())
-        .map(|(f, s)| (f - s).abs())
-        .sum();
-
-    sum
+struct TableOptions {
+    unsigned: bool,
+    pairs: Pairs,
+    metric: String,
 }
 
-// refactoring this out was a mistake wtf
-// clippy says I don't need the lifetimes, but if I remove them the compiler yells at me
-#[allow(clippy::needless_lifetimes)]
-fn parse_iter<'s>(
-    input: &'s str,
-) -> impl Iterator<Item = impl Iterator<Item = i32> + use<'s>> + use<'s> {
-    let parsed = input
-        .lines()
-        .map(|line| line.split_whitespace().map(|s| s.parse::<i32>().unwrap()));
-    parsed
+// Streams the lists from the path given on the command line, `-` for
+// stdin, or input.txt by default. Any of the table flags compares columns
+// of a wider table instead, numbered from 1.
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            eprintln!("{}", USAGE);
+            process::exit(1);
+        }
+    };
+    let result = match &options.table {
+        None => print_scores(&options.path),
+        Some(table) if table.unsigned => compare::<u64>(&options.path, table),
+        Some(table) => compare::<i64>(&options.path, table),
+    };
+    if let Err(msg) = result {
+        eprintln!("{}: {}", options.path, msg);
+        process::exit(1);
+    }
 }
 
-fn similarity_score(input: &str) -> i32 {
-    let parsed = parse_iter(input);
-
-    let mut first = vec![];
-    let mut second = HashMap::new();
-
-    for mut line in parsed {
-        first.push(line.next().unwrap());
-        second
-            .entry(line.next().unwrap())
-            .and_modify(|count| *count += 1)
-            .or_insert(1);
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut path = "input.txt".to_string();
+    let mut unsigned = None;
+    let mut pairs = None;
+    let mut metric = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || {
+            iter.next()
+                .ok_or_else(|| format!("missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--type" => match value()?.as_str() {
+                "i64" => unsigned = Some(false),
+                "u64" => unsigned = Some(true),
+                other => return Err(format!("unknown type: {}", other)),
+            },
+            "--pair" => {
+                let value = value()?;
+                let (a, b) = value
+                    .split_once(',')
+                    .and_then(|(a, b)| Some((column(a)?, column(b)?)))
+                    .ok_or_else(|| format!("--pair needs columns like 1,2, got {}", value))?;
+                pairs = Some(Pairs::One(a, b));
+            }
+            "--all-pairs" => pairs = Some(Pairs::All),
+            "--metric" => metric = Some(value()?.clone()),
+            _ if arg.starts_with("--") => return Err(format!("unknown flag: {}", arg)),
+            _ => path = arg.clone(),
+        }
     }
+    let table = if unsigned.is_some() || pairs.is_some() || metric.is_some() {
+        Some(TableOptions {
+            unsigned: unsigned.unwrap_or(false),
+            pairs: pairs.unwrap_or(Pairs::One(0, 1)),
+            metric: metric.unwrap_or_else(|| "distance".to_string()),
+        })
+    } else {
+        None
+    };
+    Ok(Options { path, table })
+}
 
-    let sum: i32 = first.iter().map(|n| second.get(n).unwrap_or(&0) * n).sum();
-
-    sum
+// 1-based on the command line, 0-based in the library
+fn column(s: &str) -> Option<usize> {
+    s.parse::<usize>().ok()?.checked_sub(1)
 }
 
-#[cfg(test)]
-mod tests {
-    // Note this useful idiom: importing names from outer (for mod tests) scope.
-    use super::*;
+fn print_scores(path: &str) -> Result<(), String> {
+    let scores = if path == "-" {
+        scores_from_reader(io::stdin().lock())
+    } else {
+        File::open(path)
+            .map_err(|e| e.into())
+            .and_then(|file| scores_from_reader(BufReader::new(file)))
+    };
+    let scores = scores.map_err(|e| e.to_string())?;
+    println!("difference: {}", scores.difference);
+    println!("similarity: {}", scores.similarity);
+    Ok(())
+}
 
-    #[test]
-    fn test_part1() {
-        let input = "3   4
-4   3
-2   5
-1   3
-3   9
-3   3";
-        let result = difference_score(input);
-        assert_eq!(result, 11);
+fn compare<T: Number>(path: &str, options: &TableOptions) -> Result<(), String> {
+    let input = if path == "-" {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input).map(|_| input)
+    } else {
+        fs::read_to_string(path)
+    };
+    let input = input.map_err(|e| e.to_string())?;
+    let table = Table::<T>::parse(&input).map_err(|e| e.to_string())?;
+    let metric: &dyn Metric<T> = match options.metric.as_str() {
+        "distance" => &SortedDistance,
+        "squared" => &SquaredDistance,
+        "similarity" => &Similarity,
+        "rank" => &RankCorrelation,
+        other => return Err(format!("unknown metric: {}", other)),
+    };
+    if let Pairs::One(i, j) = options.pairs {
+        let width = table.columns.len();
+        if i >= width || j >= width {
+            return Err(format!("the table only has {} columns", width));
+        }
     }
-
-    #[test]
-    fn test_part2() {
-        let input = "3   4
-4   3
-2   5
-1   3
-3   9
-3   3";
-        let result = similarity_score(input);
-        assert_eq!(result, 31);
+    for ((i, j), score) in table.compare(options.pairs, metric) {
+        println!("columns {},{}: {}", i + 1, j + 1, score);
     }
+    Ok(())
 }
@@ -0,0 +1,480 @@
+use std::fmt;
+
+use common::{lines, Answer, ParseError, Solution};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Vec<i32>>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, reports: &Vec<Vec<i32>>) -> Answer {
+        safe_reports(reports).into()
+    }
+
+    fn part2(&self, reports: &Vec<Vec<i32>>) -> Answer {
+        damp_safe_reports(reports).into()
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    lines(input)
+        .map(|mut line| {
+            let mut levels = Vec::new();
+            line.skip_whitespace();
+            while !line.at_end() {
+                levels.push(line.number()?);
+                line.skip_whitespace();
+            }
+            Ok(levels)
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Either,
+    Increasing,
+    Decreasing,
+}
+
+// The rules a report has to follow to be safe. The default is the puzzle's:
+// levels all increasing or all decreasing, by 1 to 3 at a time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SafetyPolicy {
+    // bounds on the size of each step between neighbouring levels
+    pub min_step: i32,
+    pub max_step: i32,
+    // equal neighbours are let through regardless of min_step
+    pub allow_equal: bool,
+    // every step has to go the same way as the first one
+    pub monotonic: bool,
+    pub trend: Trend,
+}
+
+impl Default for SafetyPolicy {
+    fn default() -> Self {
+        SafetyPolicy {
+            min_step: 1,
+            max_step: 3,
+            allow_equal: false,
+            monotonic: true,
+            trend: Trend::Either,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    StepTooSmall,
+    StepTooLarge,
+    ZeroStep,
+    DirectionChange,
+    WrongTrend,
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Rule::StepTooSmall => "step too small",
+            Rule::StepTooLarge => "step too large",
+            Rule::ZeroStep => "zero step",
+            Rule::DirectionChange => "direction change",
+            Rule::WrongTrend => "against the trend",
+        };
+        write!(f, "{}", msg)
+    }
+}
+
+// Levels `index` and `index + 1` of a report, and the rule they break.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub index: usize,
+    pub levels: (i32, i32),
+    pub diff: i32,
+    pub rule: Rule,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Safe,
+    // unsafe as it stands, but safe with the levels at `removed` dropped
+    Dampened {
+        removed: Vec<usize>,
+        violation: Violation,
+    },
+    Unsafe(Violation),
+}
+
+impl SafetyPolicy {
+    pub fn is_safe(&self, levels: &[i32]) -> bool {
+        self.first_violation(levels).is_none()
+    }
+
+    // The first pair of neighbouring levels that breaks a rule.
+    pub fn first_violation(&self, levels: &[i32]) -> Option<Violation> {
+        let mut direction = 0;
+        for (index, window) in levels.windows(2).enumerate() {
+            let diff = window[1] - window[0];
+            let broken = if diff == 0 {
+                if self.allow_equal {
+                    continue;
+                }
+                Some(Rule::ZeroStep)
+            } else if diff.abs() < self.min_step {
+                Some(Rule::StepTooSmall)
+            } else if diff.abs() > self.max_step {
+                Some(Rule::StepTooLarge)
+            } else {
+                match self.trend {
+                    Trend::Increasing if diff < 0 => Some(Rule::WrongTrend),
+                    Trend::Decreasing if diff > 0 => Some(Rule::WrongTrend),
+                    _ if self.monotonic && direction != 0 && direction != diff.signum() => {
+                        Some(Rule::DirectionChange)
+                    }
+                    _ => None,
+                }
+            };
+            if let Some(rule) = broken {
+                return Some(Violation {
+                    index,
+                    levels: (window[0], window[1]),
+                    diff,
+                    rule,
+                });
+            }
+            direction = diff.signum();
+        }
+        None
+    }
+
+    // Whether the report is safe as it is, once the dampener has dropped
+    // some levels, or not at all, and why.
+    pub fn verdict(&self, levels: &[i32], tolerance: usize) -> Verdict {
+        let Some(violation) = self.first_violation(levels) else {
+            return Verdict::Safe;
+        };
+        match self.dampen(levels, tolerance) {
+            Some(removed) => Verdict::Dampened { removed, violation },
+            None => Verdict::Unsafe(violation),
+        }
+    }
+
+    // Safe, or safe once any one level is taken out.
+    pub fn is_damp_safe(&self, levels: &[i32]) -> bool {
+        self.dampen(levels, 1).is_some()
+    }
+
+    // The fewest levels (at most `tolerance`) whose removal makes the report
+    // safe, as sorted indices, or None if it takes more than that.
+    //
+    // For each level, and each direction the report may run in, this finds
+    // the fewest removals that leave a safe run ending on that level. Only
+    // the last `tolerance + 1` levels before it can be its kept neighbour,
+    // so that is O(n * tolerance) steps per direction.
+    pub fn dampen(&self, levels: &[i32], tolerance: usize) -> Option<Vec<usize>> {
+        let directions: &[Direction] = match (self.trend, self.monotonic) {
+            (Trend::Increasing, _) => &[Direction::Up],
+            (Trend::Decreasing, _) => &[Direction::Down],
+            (Trend::Either, true) => &[Direction::Up, Direction::Down],
+            (Trend::Either, false) => &[Direction::Any],
+        };
+        directions
+            .iter()
+            .filter_map(|dir| self.dampen_towards(levels, tolerance, *dir))
+            .min_by_key(Vec::len)
+    }
+
+    fn dampen_towards(
+        &self,
+        levels: &[i32],
+        tolerance: usize,
+        dir: Direction,
+    ) -> Option<Vec<usize>> {
+        let n = levels.len();
+        if n == 0 {
+            return Some(Vec::new());
+        }
+        // removals[i]: fewest removals before i with i kept, prev[i]: the
+        // level kept before i in that run
+        let mut removals = vec![usize::MAX; n];
+        let mut prev = vec![None; n];
+        for i in 0..n {
+            if i <= tolerance {
+                removals[i] = i;
+            }
+            for j in (i.saturating_sub(tolerance + 1)..i).rev() {
+                if removals[j] == usize::MAX || !self.step_ok(levels[j], levels[i], dir) {
+                    continue;
+                }
+                let total = removals[j] + (i - j - 1);
+                if total < removals[i] {
+                    removals[i] = total;
+                    prev[i] = Some(j);
+                }
+            }
+        }
+        let last = (0..n)
+            .filter(|&i| removals[i] != usize::MAX)
+            .min_by_key(|&i| removals[i] + (n - 1 - i))?;
+        if removals[last] + (n - 1 - last) > tolerance {
+            return None;
+        }
+        let mut kept = vec![false; n];
+        let mut i = Some(last);
+        while let Some(k) = i {
+            kept[k] = true;
+            i = prev[k];
+        }
+        Some((0..n).filter(|&i| !kept[i]).collect())
+    }
+
+    fn step_ok(&self, from: i32, to: i32, dir: Direction) -> bool {
+        let diff = to - from;
+        if diff == 0 {
+            return self.allow_equal;
+        }
+        let direction_ok = match dir {
+            Direction::Up => diff > 0,
+            Direction::Down => diff < 0,
+            Direction::Any => true,
+        };
+        direction_ok && diff.abs() >= self.min_step && diff.abs() <= self.max_step
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Direction {
+    Up,
+    Down,
+    Any,
+}
+
+fn safe_reports(reports: &[Vec<i32>]) -> i32 {
+    safe_reports_with(reports, &SafetyPolicy::default())
+}
+
+pub fn safe_reports_with(reports: &[Vec<i32>], policy: &SafetyPolicy) -> i32 {
+    count(reports, |report| policy.is_safe(report))
+}
+
+pub fn verdicts(reports: &[Vec<i32>], policy: &SafetyPolicy, tolerance: usize) -> Vec<Verdict> {
+    reports
+        .iter()
+        .map(|report| policy.verdict(report, tolerance))
+        .collect()
+}
+
+fn count(reports: &[Vec<i32>], f: impl Fn(&[i32]) -> bool) -> i32 {
+    reports.iter().map(|v| f(v)).fold(0, |acc, report| {
+        let mut result = acc;
+        if report {
+            result += 1;
+        }
+        result
+    })
+}
+
+fn damp_safe_reports(reports: &[Vec<i32>]) -> i32 {
+    damp_safe_reports_with(reports, &SafetyPolicy::default(), 1)
+}
+
+// Counts the reports that are safe once at most `tolerance` levels are dropped.
+pub fn damp_safe_reports_with(
+    reports: &[Vec<i32>],
+    policy: &SafetyPolicy,
+    tolerance: usize,
+) -> i32 {
+    count(reports, |report| policy.dampen(report, tolerance).is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    fn is_safe(levels: &[i32]) -> bool {
+        SafetyPolicy::default().is_safe(levels)
+    }
+
+    fn is_damp_safe(levels: &[i32]) -> bool {
+        SafetyPolicy::default().is_damp_safe(levels)
+    }
+
+    #[test]
+    fn test_part1() {
+        let input = "7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9";
+        let result = safe_reports(&parse(input).unwrap());
+        assert_eq!(result, 2);
+    }
+
+    #[test]
+    fn test_is_safe1() {
+        let input = [7, 6, 4, 2, 1];
+        let result = is_safe(&input);
+        assert!(result);
+    }
+
+    #[test]
+    fn test_is_safe2() {
+        let input = [1, 2, 7, 8, 9];
+        let result = is_safe(&input);
+        assert!(!result);
+    }
+
+    #[test]
+    fn test_is_safe3() {
+        let input = [9, 7, 6, 2, 1];
+        let result = is_safe(&input);
+        assert!(!result);
+    }
+
+    #[test]
+    fn test_is_safe4() {
+        let input = [1, 3, 2, 4, 5];
+        let result = is_safe(&input);
+        assert!(!result);
+    }
+
+    #[test]
+    fn test_is_safe5() {
+        let input = [8, 6, 4, 4, 1];
+        let result = is_safe(&input);
+        assert!(!result);
+    }
+
+    #[test]
+    fn test_is_safe6() {
+        let input = [1, 3, 6, 7, 9];
+        let result = is_safe(&input);
+        assert!(result);
+    }
+
+    #[test]
+    fn test_policy() {
+        let reports = parse("1 2 2 3\n1 5 6\n3 2 1").unwrap();
+        let policy = SafetyPolicy {
+            max_step: 4,
+            allow_equal: true,
+            trend: Trend::Increasing,
+            ..SafetyPolicy::default()
+        };
+        assert_eq!(safe_reports_with(&reports, &policy), 2);
+        let policy = SafetyPolicy {
+            monotonic: false,
+            ..SafetyPolicy::default()
+        };
+        assert!(policy.is_safe(&[1, 3, 2, 4]));
+        assert!(!policy.is_safe(&[1, 3, 3, 4]));
+    }
+
+    #[test]
+    fn test_dampen() {
+        let policy = SafetyPolicy::default();
+        assert_eq!(policy.dampen(&[1, 3, 2, 4, 5], 1), Some(vec![1]));
+        assert_eq!(policy.dampen(&[8, 6, 4, 4, 1], 1), Some(vec![2]));
+        assert_eq!(policy.dampen(&[7, 6, 4, 2, 1], 1), Some(vec![]));
+        assert_eq!(policy.dampen(&[1, 9, 2, 9, 3], 1), None);
+        assert_eq!(policy.dampen(&[1, 9, 2, 9, 3], 2), Some(vec![1, 3]));
+        // dropping the first or last level is allowed too
+        assert_eq!(policy.dampen(&[9, 1, 2, 3, 4, 0], 2), Some(vec![0, 5]));
+    }
+
+    #[test]
+    fn test_verdicts() {
+        let reports = parse("7 6 4 2 1\n1 2 7 8 9\n1 3 2 4 5\n8 6 4 4 1").unwrap();
+        let verdicts = verdicts(&reports, &SafetyPolicy::default(), 1);
+        assert_eq!(
+            verdicts,
+            vec![
+                Verdict::Safe,
+                Verdict::Unsafe(Violation {
+                    index: 1,
+                    levels: (2, 7),
+                    diff: 5,
+                    rule: Rule::StepTooLarge,
+                }),
+                Verdict::Dampened {
+                    removed: vec![1],
+                    violation: Violation {
+                        index: 1,
+                        levels: (3, 2),
+                        diff: -1,
+                        rule: Rule::DirectionChange,
+                    },
+                },
+                Verdict::Dampened {
+                    removed: vec![2],
+                    violation: Violation {
+                        index: 2,
+                        levels: (4, 4),
+                        diff: 0,
+                        rule: Rule::ZeroStep,
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9";
+        let result = damp_safe_reports(&parse(input).unwrap());
+        assert_eq!(result, 4);
+    }
+
+    #[test]
+    fn test_is_damp_safe1() {
+        let input = [7, 6, 4, 2, 1];
+        let result = is_damp_safe(&input);
+        assert!(result);
+    }
+
+    #[test]
+    fn test_is_damp_safe2() {
+        let input = [1, 2, 7, 8, 9];
+        let result = is_damp_safe(&input);
+        assert!(!result);
+    }
+
+    #[test]
+    fn test_is_damp_safe3() {
+        let input = [9, 7, 6, 2, 1];
+        let result = is_damp_safe(&input);
+        assert!(!result);
+    }
+
+    #[test]
+    fn test_is_damp_safe4() {
+        let input = [1, 3, 2, 4, 5];
+        let result = is_damp_safe(&input);
+        assert!(result);
+    }
+
+    #[test]
+    fn test_is_damp_safe5() {
+        let input = [8, 6, 4, 4, 1];
+        let result = is_damp_safe(&input);
+        assert!(result);
+    }
+
+    #[test]
+    fn test_is_damp_safe6() {
+        let input = [1, 3, 6, 7, 9];
+        let result = is_damp_safe(&input);
+        assert!(result);
+    }
+}
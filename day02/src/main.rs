@@ -1,198 +1,133 @@
-use std::fs;
+use std::{env, process, str::FromStr};
 
+use day02::{
+    damp_safe_reports_with, safe_reports_with, verdicts, SafetyPolicy, Trend, Verdict, Violation,
+};
+
+const USAGE: &str = "usage: day02 [--min-step N] [--max-step N] [--allow-equal] \
+                     [--any-direction] [--trend increasing|decreasing|either] [--tolerance N] [--explain]";
+
+struct Options {
+    policy: SafetyPolicy,
+    // how many levels the Problem Dampener may drop
+    tolerance: usize,
+    // print a verdict for every report before the counts
+    explain: bool,
+}
+
+// With no flags the reports are checked against the puzzle's rules.
 fn main() {
-    let input = fs::read_to_string("input.txt").unwrap();
+    let args: Vec<String> = env::args().skip(1).collect();
+    let Options {
+        policy,
+        tolerance,
+        explain,
+    } = match parse_args(&args) {
+        Ok(options) => options,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            eprintln!("{}", USAGE);
+            process::exit(1);
+        }
+    };
+    let reports = common::parse_file(&day02::Day02, "input.txt");
+
+    if explain {
+        print_verdicts(&reports, &policy, tolerance);
+    }
 
-    let num_safe = safe_reports(&input);
+    let num_safe = safe_reports_with(&reports, &policy);
     println!("Number of safe reports: {}", num_safe);
 
-    let num_damp_safe = damp_safe_reports(&input);
+    let num_damp_safe = damp_safe_reports_with(&reports, &policy, tolerance);
     println!(
         "Number of safe reports with Problem Dampener: {}",
         num_damp_safe
     );
 }
 
-fn safe_reports(input: &str) -> i32 {
-    safe_reports_with(input, is_safe)
-}
-
-fn safe_reports_with(input: &str, f: impl Fn(&[i32]) -> bool) -> i32 {
-    input
-        .lines()
-        .map(|s| {
-            s.split_whitespace()
-                .map(|s| s.parse::<i32>().unwrap())
-                .collect::<Vec<_>>()
-        })
-        .map(|v| f(&v))
-        .fold(0, |acc, report| {
-            let mut result = acc;
-            if report {
-                result += 1;
-            }
-            result
-        })
-}
-
-fn is_safe(levels: &[i32]) -> bool {
-    levels
-        .windows(2)
-        .map(|window| window[0] - window[1])
-        .map(|diff| {
-            if diff > 3 || diff == 0 || diff < -3 {
-                Err(diff)
-            } else {
-                Ok(diff)
-            }
-        })
-        .try_fold(0, |acc, level| {
-            if acc == 0 {
-                Ok(level?)
-            } else {
-                match (acc > 0, level? > 0) {
-                    (false, false) => Ok(acc),
-                    (true, true) => Ok(acc),
-                    _ => Err(0),
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut policy = SafetyPolicy::default();
+    let mut tolerance = 1;
+    let mut explain = false;
+    let mut iter = args.iter();
+    while let Some(flag) = iter.next() {
+        let mut value = || {
+            iter.next()
+                .ok_or_else(|| format!("missing value for {}", flag))
+        };
+        match flag.as_str() {
+            "--min-step" => policy.min_step = parse_number(flag, value()?)?,
+            "--max-step" => policy.max_step = parse_number(flag, value()?)?,
+            "--tolerance" => tolerance = parse_number(flag, value()?)?,
+            "--explain" => explain = true,
+            "--allow-equal" => policy.allow_equal = true,
+            "--any-direction" => policy.monotonic = false,
+            "--trend" => {
+                policy.trend = match value()?.as_str() {
+                    "increasing" => Trend::Increasing,
+                    "decreasing" => Trend::Decreasing,
+                    "either" => Trend::Either,
+                    other => return Err(format!("unknown trend: {}", other)),
                 }
             }
-        })
-        .is_ok()
-}
-
-fn damp_safe_reports(input: &str) -> i32 {
-    safe_reports_with(input, is_damp_safe)
-}
-
-fn is_damp_safe(levels: &[i32]) -> bool {
-    if is_safe(levels) {
-        return true;
-    }
-    for i in 0..levels.len() {
-        let mut damp_report = Vec::with_capacity(levels.len() - 1);
-        let split = levels.split_at(i);
-        damp_report.extend_from_slice(split.0);
-        let final_slice = split.1.get(1..);
-        if let Some(slice) = final_slice {
-            damp_report.extend_from_slice(slice);
-        }
-        if is_safe(&damp_report) {
-            return true;
+            _ => return Err(format!("unknown flag: {}", flag)),
         }
     }
-    false
+    Ok(Options {
+        policy,
+        tolerance,
+        explain,
+    })
 }
 
-#[cfg(test)]
-mod tests {
-    // Note this useful idiom: importing names from outer (for mod tests) scope.
-    use super::*;
-
-    #[test]
-    fn test_part1() {
-        let input = "7 6 4 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9";
-        let result = safe_reports(input);
-        assert_eq!(result, 2);
-    }
-
-    #[test]
-    fn test_is_safe1() {
-        let input = [7, 6, 4, 2, 1];
-        let result = is_safe(&input);
-        assert!(result);
-    }
-
-    #[test]
-    fn test_is_safe2() {
-        let input = [1, 2, 7, 8, 9];
-        let result = is_safe(&input);
-        assert!(!result);
-    }
-
-    #[test]
-    fn test_is_safe3() {
-        let input = [9, 7, 6, 2, 1];
-        let result = is_safe(&input);
-        assert!(!result);
-    }
-
-    #[test]
-    fn test_is_safe4() {
-        let input = [1, 3, 2, 4, 5];
-        let result = is_safe(&input);
-        assert!(!result);
-    }
-
-    #[test]
-    fn test_is_safe5() {
-        let input = [8, 6, 4, 4, 1];
-        let result = is_safe(&input);
-        assert!(!result);
-    }
-
-    #[test]
-    fn test_is_safe6() {
-        let input = [1, 3, 6, 7, 9];
-        let result = is_safe(&input);
-        assert!(result);
-    }
-
-    #[test]
-    fn test_part2() {
-        let input = "7 6 4 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9";
-        let result = damp_safe_reports(input);
-        assert_eq!(result, 4);
-    }
-
-    #[test]
-    fn test_is_damp_safe1() {
-        let input = [7, 6, 4, 2, 1];
-        let result = is_damp_safe(&input);
-        assert!(result);
-    }
-
-    #[test]
-    fn test_is_damp_safe2() {
-        let input = [1, 2, 7, 8, 9];
-        let result = is_damp_safe(&input);
-        assert!(!result);
-    }
-
-    #[test]
-    fn test_is_damp_safe3() {
-        let input = [9, 7, 6, 2, 1];
-        let result = is_damp_safe(&input);
-        assert!(!result);
-    }
-
-    #[test]
-    fn test_is_damp_safe4() {
-        let input = [1, 3, 2, 4, 5];
-        let result = is_damp_safe(&input);
-        assert!(result);
-    }
+fn parse_number<T: FromStr + Default + PartialOrd>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .ok()
+        .filter(|n| *n >= T::default())
+        .ok_or_else(|| format!("{} must be a non-negative number, got {}", flag, value))
+}
 
-    #[test]
-    fn test_is_damp_safe5() {
-        let input = [8, 6, 4, 4, 1];
-        let result = is_damp_safe(&input);
-        assert!(result);
+// One line per report, numbered like the input lines. Levels are numbered
+// from 1 as well.
+fn print_verdicts(reports: &[Vec<i32>], policy: &SafetyPolicy, tolerance: usize) {
+    println!("{:>6}  {:<8}  detail", "report", "verdict");
+    for (i, verdict) in verdicts(reports, policy, tolerance).iter().enumerate() {
+        let (name, detail) = match verdict {
+            Verdict::Safe => ("safe", String::new()),
+            Verdict::Unsafe(violation) => ("unsafe", describe(violation)),
+            Verdict::Dampened { removed, violation } => {
+                let removed: Vec<String> = removed
+                    .iter()
+                    .map(|&level| format!("{} ({})", level + 1, reports[i][level]))
+                    .collect();
+                let noun = if removed.len() == 1 {
+                    "level"
+                } else {
+                    "levels"
+                };
+                let detail = format!(
+                    "{}; removed {} {}",
+                    describe(violation),
+                    noun,
+                    removed.join(", ")
+                );
+                ("dampened", detail)
+            }
+        };
+        println!("{:>6}  {:<8}  {}", i + 1, name, detail);
     }
+}
 
-    #[test]
-    fn test_is_damp_safe6() {
-        let input = [1, 3, 6, 7, 9];
-        let result = is_damp_safe(&input);
-        assert!(result);
-    }
+fn describe(violation: &Violation) -> String {
+    format!(
+        "levels {}-{}: {} -> {} ({:+}), {}",
+        violation.index + 1,
+        violation.index + 2,
+        violation.levels.0,
+        violation.levels.1,
+        violation.diff,
+        violation.rule
+    )
 }
@@ -0,0 +1,224 @@
+use std::ops::RangeInclusive;
+
+// What running an instruction does to the machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    Enable,
+    Disable,
+    Add(i64),
+}
+
+// One instruction the scanner recognises, written `name(arg,arg,...)`
+// with no whitespace anywhere. Arguments are unsigned numbers of 1 to
+// `max_digits` digits.
+#[derive(Debug, Clone)]
+pub struct Instruction {
+    pub name: &'static str,
+    pub arity: RangeInclusive<usize>,
+    pub max_digits: usize,
+    pub eval: fn(&[i64]) -> Effect,
+}
+
+// An instruction as it was found, with its arguments already evaluated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Operator {
+    pub name: &'static str,
+    pub args: Vec<i64>,
+    pub effect: Effect,
+}
+
+// The instructions to look for. When more than one could match at the same
+// place, the one registered first wins.
+#[derive(Debug, Clone, Default)]
+pub struct InstructionSet {
+    instructions: Vec<Instruction>,
+}
+
+impl InstructionSet {
+    pub fn new() -> InstructionSet {
+        InstructionSet::default()
+    }
+
+    // The puzzle's mul(a,b), do() and don't(). A product too big for i64,
+    // possible once `max_digits` is raised, saturates.
+    pub fn standard() -> InstructionSet {
+        let mut set = InstructionSet::new();
+        set.register(Instruction {
+            name: "mul",
+            arity: 2..=2,
+            max_digits: 3,
+            eval: |args| Effect::Add(args[0].saturating_mul(args[1])),
+        })
+        .register(Instruction {
+            name: "do",
+            arity: 0..=0,
+            max_digits: 0,
+            eval: |_| Effect::Enable,
+        })
+        .register(Instruction {
+            name: "don't",
+            arity: 0..=0,
+            max_digits: 0,
+            eval: |_| Effect::Disable,
+        });
+        set
+    }
+
+    pub fn register(&mut self, instruction: Instruction) -> &mut InstructionSet {
+        self.instructions.push(instruction);
+        self
+    }
+
+    // What starts at the front of `bytes`. If the bytes run out while an
+    // instruction still could match, it is `Partial` rather than `Missing`.
+    pub(crate) fn match_at(&self, bytes: &[u8]) -> Match {
+        self.instructions
+            .iter()
+            .map(|instruction| instruction.match_at(bytes))
+            .find(|found| *found != Match::Missing)
+            .unwrap_or(Match::Missing)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Match {
+    // the instruction and its length in bytes
+    Found(Operator, usize),
+    Missing,
+    Partial,
+}
+
+impl Instruction {
+    fn match_at(&self, bytes: &[u8]) -> Match {
+        let mut scan = Scan { bytes, pos: 0 };
+        match self.scan(&mut scan) {
+            Ok(args) if self.arity.contains(&args.len()) => {
+                let effect = (self.eval)(&args);
+                let op = Operator {
+                    name: self.name,
+                    args,
+                    effect,
+                };
+                Match::Found(op, scan.pos)
+            }
+            Ok(_) => Match::Missing,
+            Err(found) => found,
+        }
+    }
+
+    fn scan(&self, scan: &mut Scan) -> Result<Vec<i64>, Match> {
+        scan.literal(self.name.as_bytes())?;
+        scan.literal(b"(")?;
+        let mut args = Vec::new();
+        match scan.literal(b")") {
+            Ok(()) => return Ok(args),
+            Err(Match::Partial) => return Err(Match::Partial),
+            Err(_) => (),
+        }
+        loop {
+            args.push(scan.number(self.max_digits)?);
+            match scan.literal(b")") {
+                Ok(()) => return Ok(args),
+                Err(Match::Partial) => return Err(Match::Partial),
+                Err(_) => scan.literal(b",")?,
+            }
+        }
+    }
+}
+
+struct Scan<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Scan<'_> {
+    fn rest(&self) -> &[u8] {
+        &self.bytes[self.pos..]
+    }
+
+    fn literal(&mut self, literal: &[u8]) -> Result<(), Match> {
+        if self.rest().starts_with(literal) {
+            self.pos += literal.len();
+            Ok(())
+        } else if literal.starts_with(self.rest()) {
+            Err(Match::Partial)
+        } else {
+            Err(Match::Missing)
+        }
+    }
+
+    // 1 to `max_digits` digits, no sign and no whitespace
+    fn number(&mut self, max_digits: usize) -> Result<i64, Match> {
+        let digits = self
+            .rest()
+            .iter()
+            .take(max_digits)
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        if digits < max_digits && digits == self.rest().len() {
+            // more digits may be on the way
+            return Err(Match::Partial);
+        }
+        if digits == 0 {
+            return Err(Match::Missing);
+        }
+        let num = self.rest()[..digits]
+            .iter()
+            .try_fold(0i64, |acc, b| {
+                acc.checked_mul(10)?.checked_add(i64::from(b - b'0'))
+            })
+            .ok_or(Match::Missing)?;
+        self.pos += digits;
+        Ok(num)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::{tokenize_with, Machine};
+
+    #[test]
+    fn test_custom_instructions() {
+        let mut set = InstructionSet::standard();
+        set.register(Instruction {
+            name: "mul",
+            arity: 2..=usize::MAX,
+            max_digits: 3,
+            eval: |args| Effect::Add(args.iter().product()),
+        })
+        .register(Instruction {
+            name: "sub",
+            arity: 2..=2,
+            max_digits: 5,
+            eval: |args| Effect::Add(-args[0] * args[1]),
+        });
+        let input = "mul(2,3,4)sub(10000,2)mul(1)mul(2,3)sub(1, 2)";
+        let effects: Vec<_> = tokenize_with(&set, input)
+            .into_iter()
+            .map(|token| token.op.effect)
+            .collect();
+        assert_eq!(
+            effects,
+            vec![Effect::Add(24), Effect::Add(-20000), Effect::Add(6)]
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        let mut set = InstructionSet::new();
+        for instruction in InstructionSet::standard().instructions {
+            set.register(Instruction {
+                max_digits: 19,
+                ..instruction
+            });
+        }
+        let input = "mul(9999999999,9999999999)mul(1,1)mul(2,3)";
+        let mut machine = Machine::new();
+        for token in tokenize_with(&set, input) {
+            machine.run(&token.op);
+        }
+        assert_eq!(machine.total, i64::MAX);
+    }
+}
@@ -0,0 +1,176 @@
+use std::io::{self, Read};
+
+use common::{Answer, ParseError, Solution};
+
+mod instructions;
+mod scanner;
+mod trace;
+pub use instructions::{Effect, Instruction, InstructionSet, Operator};
+pub use scanner::{scan_reader, tokenize, tokenize_with, Token};
+pub use trace::{TraceEntry, Tracer};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<Operator>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Operator>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, ops: &Vec<Operator>) -> Answer {
+        sum_of_mults(ops).into()
+    }
+
+    fn part2(&self, ops: &Vec<Operator>) -> Answer {
+        dos_and_donts(ops).into()
+    }
+}
+
+// corrupted memory is expected, anything that isn't an instruction is skipped
+fn parse(input: &str) -> Result<Vec<Operator>, ParseError> {
+    Ok(tokenize(input).into_iter().map(|token| token.op).collect())
+}
+
+fn sum_of_mults(ops: &[Operator]) -> i64 {
+    let mut machine = Machine::unconditional();
+    for op in ops {
+        machine.run(op);
+    }
+    machine.total
+}
+
+fn dos_and_donts(ops: &[Operator]) -> i64 {
+    let mut machine = Machine::new();
+    for op in ops {
+        machine.run(op);
+    }
+    machine.total
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Totals {
+    // every mul(), as in part 1
+    pub all: i64,
+    // only the mul()s that were switched on, as in part 2
+    pub enabled: i64,
+}
+
+// Both parts straight from a reader, `chunk_size` bytes at a time. The
+// machines live across chunks, so a don't() in one chunk still switches
+// off the mul()s in the next.
+pub fn evaluate_reader(
+    set: &InstructionSet,
+    reader: impl Read,
+    chunk_size: usize,
+) -> io::Result<Totals> {
+    let mut all = Machine::unconditional();
+    let mut enabled = Machine::new();
+    scan_reader(set, reader, chunk_size, |token| {
+        all.run(&token.op);
+        enabled.run(&token.op);
+    })?;
+    Ok(Totals {
+        all: all.total,
+        enabled: enabled.total,
+    })
+}
+
+// Runs instructions one at a time, keeping the running total. The total
+// saturates rather than overflowing i64.
+#[derive(Debug, Clone)]
+pub struct Machine {
+    pub enabled: bool,
+    pub total: i64,
+    // whether do() and don't() are obeyed at all
+    conditionals: bool,
+}
+
+impl Default for Machine {
+    fn default() -> Self {
+        Machine::new()
+    }
+}
+
+impl Machine {
+    pub fn new() -> Machine {
+        Machine {
+            enabled: true,
+            total: 0,
+            conditionals: true,
+        }
+    }
+
+    // A machine that multiplies everything, ignoring do() and don't().
+    pub fn unconditional() -> Machine {
+        Machine {
+            conditionals: false,
+            ..Machine::new()
+        }
+    }
+
+    // Returns whether the instruction took effect, which a mul() doesn't
+    // while the machine is switched off.
+    pub fn run(&mut self, op: &Operator) -> bool {
+        match op.effect {
+            Effect::Enable => self.enabled = true,
+            Effect::Disable => self.enabled = !self.conditionals,
+            Effect::Add(n) => {
+                if self.enabled {
+                    self.total = self.total.saturating_add(n);
+                }
+                return self.enabled;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = r"xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+        let answer = sum_of_mults(&parse(input).unwrap());
+        assert_eq!(answer, 161);
+    }
+
+    #[test]
+    fn test_no_whitespace() {
+        let input = "mul ( 2 , 4 )";
+        let answer = sum_of_mults(&parse(input).unwrap());
+        assert_eq!(answer, 0);
+
+        let input = "mul( 2 , 4 )";
+        let answer = sum_of_mults(&parse(input).unwrap());
+        assert_eq!(answer, 0);
+
+        let input = "mul(2, 4)";
+        let answer = sum_of_mults(&parse(input).unwrap());
+        assert_eq!(answer, 0);
+    }
+
+    #[test]
+    fn test_evaluate_reader() {
+        let input = r"xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        let set = InstructionSet::standard();
+        let totals = evaluate_reader(&set, input.as_bytes(), 5).unwrap();
+        assert_eq!(
+            totals,
+            Totals {
+                all: 161,
+                enabled: 48
+            }
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = r"xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        let result = dos_and_donts(&parse(input).unwrap());
+        assert_eq!(result, 48);
+    }
+}
@@ -1,90 +1,42 @@
-use regex::*;
-use std::fs::read_to_string;
+use std::{env, fs::File, process};
 
-fn main() {
-    let input = read_to_string("input.txt").unwrap();
-    let answer = sum_of_mults(&input);
-    println!("Sum of mults: {}", answer);
-
-    let part2 = dos_and_donts(&input);
-    println!("Part 2: {}", part2);
-}
-
-fn sum_of_mults(input: &str) -> i32 {
-    let reg = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
-    reg.captures_iter(input)
-        .map(|cap| {
-            let (_, [l, r]) = cap.extract();
-            let l: i32 = l.parse().unwrap();
-            let r: i32 = r.parse().unwrap();
-            l * r
-        })
-        .sum()
-}
-
-fn dos_and_donts(input: &str) -> i32 {
-    let reg = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)|do\(\)()()|don't\(\)()()").unwrap();
-    let (_, result) = reg
-        .captures_iter(input)
-        .map(|cap| {
-            let (group, [l, r]) = cap.extract();
-            match group {
-                "do()" => Operator::Do,
-                "don't()" => Operator::Dont,
-                _ => Operator::Mult(l.parse().unwrap(), r.parse().unwrap()),
-            }
-        })
-        .fold((true, 0), |(on_off, acc), op| match op {
-            Operator::Do => (true, acc),
-            Operator::Dont => (false, acc),
-            Operator::Mult(l, r) => {
-                if on_off {
-                    (on_off, acc + (l * r))
-                } else {
-                    (on_off, acc)
-                }
-            }
-        });
-    result
-}
+use day03::{evaluate_reader, scan_reader, InstructionSet, Machine, TraceEntry, Tracer};
 
-enum Operator {
-    Do,
-    Dont,
-    Mult(i32, i32),
-}
+// big enough to read quickly, small enough that memory dumps of any size fit
+const CHUNK_SIZE: usize = 64 * 1024;
 
-#[cfg(test)]
-mod tests {
-    // Note this useful idiom: importing names from outer (for mod tests) scope.
-    use super::*;
+const USAGE: &str = "usage: day03 [--trace text|json]";
 
-    #[test]
-    fn test_part1() {
-        let input = r"xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
-        let answer = sum_of_mults(input);
-        assert_eq!(answer, 161);
-    }
-
-    #[test]
-    fn test_no_whitespace() {
-        let input = "mul ( 2 , 4 )";
-        let answer = sum_of_mults(input);
-        assert_eq!(answer, 0);
-
-        let input = "mul( 2 , 4 )";
-        let answer = sum_of_mults(input);
-        assert_eq!(answer, 0);
-
-        let input = "mul(2, 4)";
-        let answer = sum_of_mults(input);
-        assert_eq!(answer, 0);
-    }
-
-    #[test]
-    fn test_part2() {
-        let input = r"xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
-        let result = dos_and_donts(input);
-        assert_eq!(result, 48);
+// `--trace` prints every instruction of part 2 as it is run instead of the
+// answers, either as a table or as JSON lines.
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let trace: Option<fn(&TraceEntry) -> String> = match args.as_slice() {
+        [] => None,
+        [flag, format] if flag == "--trace" && format == "text" => Some(TraceEntry::text),
+        [flag, format] if flag == "--trace" && format == "json" => Some(TraceEntry::json),
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(1);
+        }
+    };
+    let set = InstructionSet::standard();
+    let result = File::open("input.txt").and_then(|file| match trace {
+        Some(format) => {
+            let mut tracer = Tracer::new(Machine::new());
+            scan_reader(&set, file, CHUNK_SIZE, |token| {
+                println!("{}", format(&tracer.step(token)));
+            })
+        }
+        None => {
+            let totals = evaluate_reader(&set, file, CHUNK_SIZE)?;
+            println!("Sum of mults: {}", totals.all);
+            println!("Part 2: {}", totals.enabled);
+            Ok(())
+        }
+    });
+    if let Err(e) = result {
+        eprintln!("input.txt: {}", e);
+        process::exit(1);
     }
 }
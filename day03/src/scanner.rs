@@ -0,0 +1,160 @@
+use std::{
+    io::{self, ErrorKind, Read},
+    ops::Range,
+};
+
+use crate::{instructions::Match, InstructionSet, Operator};
+
+// An instruction found in the corrupted memory, and the bytes it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub op: Operator,
+    pub span: Range<usize>,
+}
+
+// Finds the puzzle's instructions, see `tokenize_with`.
+pub fn tokenize(input: &str) -> Vec<Token> {
+    tokenize_with(&InstructionSet::standard(), input)
+}
+
+// Finds every well formed instruction, reading left to right. Anything
+// else is corruption and is skipped a byte at a time.
+pub fn tokenize_with(set: &InstructionSet, input: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    scan(set, input.as_bytes(), 0, true, &mut |token| {
+        tokens.push(token)
+    });
+    tokens
+}
+
+// Like `tokenize_with`, but reading `chunk_size` bytes at a time and handing
+// each token over as soon as it is complete. An instruction cut in two by
+// the end of a chunk is held back until the next one arrives, so memory is
+// bounded by the chunk size plus the longest instruction. Spans count bytes
+// from the start of the stream.
+pub fn scan_reader(
+    set: &InstructionSet,
+    mut reader: impl Read,
+    chunk_size: usize,
+    mut on_token: impl FnMut(Token),
+) -> io::Result<()> {
+    let mut buf = Vec::with_capacity(chunk_size * 2);
+    let mut chunk = vec![0; chunk_size.max(1)];
+    let mut offset = 0;
+    loop {
+        let read = match reader.read(&mut chunk) {
+            Ok(read) => read,
+            // a signal came in before anything was read, as it can on a pipe
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        buf.extend_from_slice(&chunk[..read]);
+        let at_end = read == 0;
+        let used = scan(set, &buf, offset, at_end, &mut on_token);
+        buf.drain(..used);
+        offset += used;
+        if at_end {
+            return Ok(());
+        }
+    }
+}
+
+// Scans `bytes`, which start `offset` bytes into the input, and returns how
+// many were used up. Unless this is the end of the input it stops in front
+// of an instruction that runs off the end, to try again with more bytes.
+fn scan(
+    set: &InstructionSet,
+    bytes: &[u8],
+    offset: usize,
+    at_end: bool,
+    on_token: &mut impl FnMut(Token),
+) -> usize {
+    let mut pos = 0;
+    while pos < bytes.len() {
+        match set.match_at(&bytes[pos..]) {
+            Match::Found(op, len) => {
+                on_token(Token {
+                    op,
+                    span: offset + pos..offset + pos + len,
+                });
+                pos += len;
+            }
+            Match::Partial if !at_end => break,
+            _ => pos += 1,
+        }
+    }
+    pos
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_spans() {
+        let tokens = tokenize("xdo()mul(1,23)mul(1234,5)don't()");
+        let found: Vec<_> = tokens
+            .iter()
+            .map(|token| (token.op.name, token.op.args.clone(), token.span.clone()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("do", vec![], 1..5),
+                ("mul", vec![1, 23], 5..14),
+                ("don't", vec![], 25..32),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_chunks() {
+        let input =
+            "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))mul(1,";
+        let set = InstructionSet::standard();
+        let expected = tokenize(input);
+        for chunk_size in 1..=16 {
+            let mut tokens = Vec::new();
+            scan_reader(&set, input.as_bytes(), chunk_size, |token| {
+                tokens.push(token)
+            })
+            .unwrap();
+            assert_eq!(tokens, expected, "chunk size {}", chunk_size);
+        }
+    }
+
+    // hands out its bytes one at a time, failing with `Interrupted` first
+    struct Flaky<'a> {
+        bytes: &'a [u8],
+        interrupt: bool,
+    }
+
+    impl Read for Flaky<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(ErrorKind::Interrupted.into());
+            }
+            let n = self.bytes.len().min(buf.len()).min(1);
+            buf[..n].copy_from_slice(&self.bytes[..n]);
+            self.bytes = &self.bytes[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_interrupted() {
+        let input = "mul(2,4)don't()mul(5,5)";
+        let reader = Flaky {
+            bytes: input.as_bytes(),
+            interrupt: false,
+        };
+        let mut tokens = Vec::new();
+        scan_reader(&InstructionSet::standard(), reader, 4, |token| {
+            tokens.push(token)
+        })
+        .unwrap();
+        assert_eq!(tokens, tokenize(input));
+    }
+}
@@ -0,0 +1,122 @@
+use std::fmt;
+
+use crate::{Machine, Operator, Token};
+
+// One step of an evaluation: where the instruction was, whether it took
+// effect, and the total after it ran.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+    pub token: Token,
+    pub enabled: bool,
+    pub total: i64,
+}
+
+// Runs tokens through a machine, recording each step.
+pub struct Tracer {
+    machine: Machine,
+}
+
+impl Tracer {
+    pub fn new(machine: Machine) -> Tracer {
+        Tracer { machine }
+    }
+
+    pub fn step(&mut self, token: Token) -> TraceEntry {
+        let enabled = self.machine.run(&token.op);
+        TraceEntry {
+            token,
+            enabled,
+            total: self.machine.total,
+        }
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let args: Vec<String> = self.args.iter().map(i64::to_string).collect();
+        write!(f, "{}({})", self.name, args.join(","))
+    }
+}
+
+impl TraceEntry {
+    fn status(&self) -> &'static str {
+        if self.enabled {
+            "enabled"
+        } else {
+            "skipped"
+        }
+    }
+
+    // A fixed width line for reading, e.g.
+    // `    28  mul(5,5)          skipped  total 8`
+    pub fn text(&self) -> String {
+        format!(
+            "{:>6}  {:<16}  {}  total {}",
+            self.token.span.start,
+            self.token.op.to_string(),
+            self.status(),
+            self.total
+        )
+    }
+
+    // One JSON object per line, for diffing against other tools.
+    pub fn json(&self) -> String {
+        let args: Vec<String> = self.token.op.args.iter().map(i64::to_string).collect();
+        format!(
+            "{{\"offset\":{},\"end\":{},\"name\":{},\"args\":[{}],\"status\":\"{}\",\"total\":{}}}",
+            self.token.span.start,
+            self.token.span.end,
+            json_string(self.token.op.name),
+            args.join(","),
+            self.status(),
+            self.total
+        )
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::tokenize;
+
+    #[test]
+    fn test_trace() {
+        let input = r"xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        let mut tracer = Tracer::new(Machine::new());
+        let trace: Vec<_> = tokenize(input)
+            .into_iter()
+            .map(|token| tracer.step(token))
+            .collect();
+        let text: Vec<_> = trace.iter().map(TraceEntry::text).collect();
+        assert_eq!(
+            text,
+            vec![
+                "     1  mul(2,4)          enabled  total 8",
+                "    20  don't()           enabled  total 8",
+                "    28  mul(5,5)          skipped  total 8",
+                "    48  mul(11,8)         skipped  total 8",
+                "    59  do()              enabled  total 8",
+                "    64  mul(8,5)          enabled  total 48",
+            ]
+        );
+        assert_eq!(
+            trace[2].json(),
+            r#"{"offset":28,"end":36,"name":"mul","args":[5,5],"status":"skipped","total":8}"#
+        );
+    }
+}
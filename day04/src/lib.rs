@@ -0,0 +1,149 @@
+use common::{Answer, ParseError, Solution};
+use grid::Grid;
+
+mod render;
+mod stencil;
+mod word_search;
+pub use render::{render, Highlight};
+pub use stencil::{Orientation, Placement, Stencil};
+pub use word_search::{Hit, WordSearch};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Grid<u8>;
+
+    fn parse(&self, input: &str) -> Result<Grid<u8>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, grid: &Grid<u8>) -> Answer {
+        num_xmas(grid).into()
+    }
+
+    fn part2(&self, grid: &Grid<u8>) -> Answer {
+        num_mas_cross(grid).into()
+    }
+}
+
+fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse(input, "ASCII letter", |c| {
+        c.is_ascii_alphabetic().then_some(c as u8)
+    })
+}
+
+pub fn xmas_search() -> WordSearch {
+    WordSearch::new(&["XMAS"])
+}
+
+fn num_xmas(grid: &Grid<u8>) -> usize {
+    xmas_search().find_all(grid).len()
+}
+
+// the two MAS diagonals crossing on the A, in any orientation
+const X_MAS: &str = "M.S
+.A.
+M.S";
+
+pub fn x_mas_stencil() -> Stencil {
+    Stencil::parse(X_MAS).unwrap()
+}
+
+fn num_mas_cross(grid: &Grid<u8>) -> usize {
+    x_mas_stencil().count(grid)
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = "MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+        let answer = num_xmas(&parse(input).unwrap());
+        assert_eq!(answer, 18);
+
+        // every XMAS is also a SAMX read the other way
+        let search = WordSearch::new(&["XMAS", "SAMX"]);
+        assert_eq!(search.find_all(&parse(input).unwrap()).len(), 36);
+    }
+
+    #[test]
+    fn test_render_matches() {
+        // the first worked example, with the unused letters replaced by Z
+        let expected = "..X...
+.SAMX.
+.A..A.
+XMAS.S
+.X....
+";
+        let grid = parse(&expected.replace('.', "Z")).unwrap();
+        let search = xmas_search();
+        let hits = search.find_all(&grid);
+        assert_eq!(hits.len(), 4);
+        let cells = hits.iter().flat_map(|hit| hit.cells(&grid));
+        assert_eq!(render(&grid, cells, Highlight::Dots), expected);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+        let answer = num_mas_cross(&parse(input).unwrap());
+        assert_eq!(answer, 9);
+    }
+
+    #[test]
+    fn test_simple1_part2() {
+        let input = "MOM
+OAO
+SOS";
+        let answer = num_mas_cross(&parse(input).unwrap());
+        assert_eq!(answer, 1);
+    }
+
+    #[test]
+    fn test_simple2_part2() {
+        let input = "SOM
+OAO
+SOM";
+        let answer = num_mas_cross(&parse(input).unwrap());
+        assert_eq!(answer, 1);
+    }
+
+    #[test]
+    fn test_simple3_part2() {
+        let input = "MOS
+OAO
+MOS";
+        let answer = num_mas_cross(&parse(input).unwrap());
+        assert_eq!(answer, 1);
+    }
+
+    #[test]
+    fn test_simple4_part2() {
+        let input = "SOS
+OAO
+MOM";
+        let answer = num_mas_cross(&parse(input).unwrap());
+        assert_eq!(answer, 1);
+    }
+}
@@ -0,0 +1,50 @@
+use grid::{Grid, Point};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+    // letters outside a match become '.', like the puzzle's worked examples
+    Dots,
+    // matched letters are printed in bold red, everything else as is
+    Ansi,
+}
+
+const ANSI_ON: &str = "\x1b[1;31m";
+const ANSI_OFF: &str = "\x1b[0m";
+
+pub fn render(grid: &Grid<u8>, cells: impl IntoIterator<Item = Point>, style: Highlight) -> String {
+    let mut matched = grid.map(|_| false);
+    for p in cells {
+        matched[p] = true;
+    }
+    let mut out = String::new();
+    for (p, letter) in grid.iter() {
+        let letter = *letter as char;
+        match (style, matched[p]) {
+            (Highlight::Dots, true) => out.push(letter),
+            (Highlight::Dots, false) => out.push('.'),
+            (Highlight::Ansi, true) => {
+                out.push_str(ANSI_ON);
+                out.push(letter);
+                out.push_str(ANSI_OFF);
+            }
+            (Highlight::Ansi, false) => out.push(letter),
+        }
+        if p.x == grid.width() - 1 {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_ansi() {
+        let grid = Grid::parse("AB\nCD", "letter", |c| Some(c as u8)).unwrap();
+        let out = render(&grid, [Point::new(1, 0)], Highlight::Ansi);
+        assert_eq!(out, "AB\n\x1b[1;31mC\x1b[0mD\n");
+    }
+}
@@ -0,0 +1,195 @@
+use common::ParseError;
+use grid::{Grid, Point};
+
+// A small 2D template of letters, written as text with '.' for cells that
+// can hold anything, e.g. "M.S\n.A.\nM.S". It matches in any of the eight
+// rotations/reflections of the square.
+pub struct Stencil {
+    variants: Vec<Variant>,
+}
+
+// Which way round a template matched: mirrored left to right or not, then
+// turned clockwise `turns` quarter turns. A symmetric template is only
+// tried, and reported, in the first orientation that gives its shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Orientation {
+    pub mirror: bool,
+    pub turns: usize,
+}
+
+struct Variant {
+    orientation: Orientation,
+    height: usize,
+    width: usize,
+    // (dy, dx, letter) of every non-wildcard cell, sorted
+    cells: Vec<(usize, usize, u8)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placement {
+    // top left corner of the template's bounding box
+    pub origin: Point,
+    // the grid cells the template's letters landed on
+    pub cells: Vec<Point>,
+    pub orientation: Orientation,
+}
+
+impl Stencil {
+    pub fn parse(text: &str) -> Result<Stencil, ParseError> {
+        let template = Grid::parse(text, "letter or '.'", |c| {
+            (c.is_ascii_alphabetic() || c == '.').then_some(c as u8)
+        })?;
+        let mut variants: Vec<Variant> = Vec::new();
+        for mirror in [false, true] {
+            for turns in 0..4 {
+                let variant = Variant::new(&template, mirror, turns);
+                // symmetric templates would otherwise count each placement twice
+                if !variants.iter().any(|v| v.same_shape(&variant)) {
+                    variants.push(variant);
+                }
+            }
+        }
+        Ok(Stencil { variants })
+    }
+
+    pub fn find_all(&self, grid: &Grid<u8>) -> Vec<Placement> {
+        let mut placements = Vec::new();
+        for variant in self.variants.iter() {
+            if variant.height > grid.height() || variant.width > grid.width() {
+                continue;
+            }
+            for y in 0..=grid.height() - variant.height {
+                for x in 0..=grid.width() - variant.width {
+                    let matched = variant
+                        .cells
+                        .iter()
+                        .all(|&(dy, dx, letter)| grid[Point::new(y + dy, x + dx)] == letter);
+                    if matched {
+                        placements.push(Placement {
+                            origin: Point::new(y, x),
+                            cells: variant
+                                .cells
+                                .iter()
+                                .map(|&(dy, dx, _)| Point::new(y + dy, x + dx))
+                                .collect(),
+                            orientation: variant.orientation,
+                        });
+                    }
+                }
+            }
+        }
+        placements
+    }
+
+    pub fn count(&self, grid: &Grid<u8>) -> usize {
+        self.find_all(grid).len()
+    }
+}
+
+impl Variant {
+    // The template mirrored left to right (if asked), then turned clockwise
+    // a quarter turn at a time.
+    fn new(template: &Grid<u8>, mirror: bool, turns: usize) -> Variant {
+        let (mut height, mut width) = (template.height(), template.width());
+        let mut cells: Vec<_> = template
+            .iter()
+            .filter(|(_, letter)| **letter != b'.')
+            .map(|(p, letter)| {
+                let x = if mirror { width - 1 - p.x } else { p.x };
+                (p.y, x, *letter)
+            })
+            .collect();
+        for _ in 0..turns {
+            for cell in cells.iter_mut() {
+                *cell = (cell.1, height - 1 - cell.0, cell.2);
+            }
+            (height, width) = (width, height);
+        }
+        cells.sort_unstable();
+        Variant {
+            orientation: Orientation { mirror, turns },
+            height,
+            width,
+            cells,
+        }
+    }
+
+    fn same_shape(&self, other: &Variant) -> bool {
+        (self.height, self.width, &self.cells) == (other.height, other.width, &other.cells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_variants() {
+        // an L shape has all eight orientations, a plus sign only one
+        let ell = Stencil::parse("A.\nAB").unwrap();
+        assert_eq!(ell.variants.len(), 8);
+        let plus = Stencil::parse(".A.\nAAA\n.A.").unwrap();
+        assert_eq!(plus.variants.len(), 1);
+    }
+
+    #[test]
+    fn test_find_all() {
+        let grid = Grid::parse("XAB\nXXX", "letter", |c| Some(c as u8)).unwrap();
+        let stencil = Stencil::parse("AB").unwrap();
+        let placements = stencil.find_all(&grid);
+        assert_eq!(
+            placements,
+            vec![Placement {
+                origin: Point::new(0, 1),
+                cells: vec![Point::new(0, 1), Point::new(0, 2)],
+                orientation: Orientation {
+                    mirror: false,
+                    turns: 0,
+                },
+            }]
+        );
+
+        // down, right to left and up
+        let grid = Grid::parse(
+            "XBA
+XXB
+XXA",
+            "letter",
+            |c| Some(c as u8),
+        )
+        .unwrap();
+        let orientations: Vec<_> = stencil
+            .find_all(&grid)
+            .iter()
+            .map(|placement| (placement.origin, placement.orientation))
+            .collect();
+        assert_eq!(
+            orientations,
+            vec![
+                (
+                    Point::new(0, 2),
+                    Orientation {
+                        mirror: false,
+                        turns: 1,
+                    }
+                ),
+                (
+                    Point::new(0, 1),
+                    Orientation {
+                        mirror: false,
+                        turns: 2,
+                    }
+                ),
+                (
+                    Point::new(1, 2),
+                    Orientation {
+                        mirror: false,
+                        turns: 3,
+                    }
+                ),
+            ]
+        );
+        assert!(Stencil::parse("AB\nA").is_err());
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+use grid::{Direction, Grid, Point};
+
+// A dictionary of words stored as a trie, so every ray out of a cell is
+// walked once no matter how many words share a prefix.
+pub struct WordSearch {
+    nodes: Vec<TrieNode>,
+    words: Vec<String>,
+}
+
+#[derive(Default)]
+struct TrieNode {
+    children: HashMap<u8, usize>,
+    // index into `words` if a word ends here
+    word: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hit<'a> {
+    pub word: &'a str,
+    pub start: Point,
+    pub dir: Direction,
+}
+
+impl Hit<'_> {
+    // The cells the word covers, starting from its first letter.
+    pub fn cells<T>(&self, grid: &Grid<T>) -> Vec<Point> {
+        grid.ray(self.start, self.dir)
+            .take(self.word.len())
+            .map(|(p, _)| p)
+            .collect()
+    }
+}
+
+impl WordSearch {
+    pub fn new(words: &[&str]) -> WordSearch {
+        let mut search = WordSearch {
+            nodes: vec![TrieNode::default()],
+            words: Vec::new(),
+        };
+        for word in words.iter().filter(|word| !word.is_empty()) {
+            search.insert(word);
+        }
+        search
+    }
+
+    fn insert(&mut self, word: &str) {
+        let mut node = 0;
+        for letter in word.bytes() {
+            node = match self.nodes[node].children.get(&letter) {
+                Some(&child) => child,
+                None => {
+                    self.nodes.push(TrieNode::default());
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children.insert(letter, child);
+                    child
+                }
+            };
+        }
+        if self.nodes[node].word.is_none() {
+            self.nodes[node].word = Some(self.words.len());
+            self.words.push(word.to_string());
+        }
+    }
+
+    // Every occurrence of every word, reading in all eight directions.
+    // Hits come out in grid order, then in `Direction::ALL` order. A
+    // one-letter word reads the same every way, so it is only reported
+    // once, facing the first of those directions.
+    pub fn find_all(&self, grid: &Grid<u8>) -> Vec<Hit<'_>> {
+        let mut hits = Vec::new();
+        for start in grid.points() {
+            // cheap check before trying all the directions
+            if !self.nodes[0].children.contains_key(&grid[start]) {
+                continue;
+            }
+            for (i, dir) in Direction::ALL.into_iter().enumerate() {
+                let mut node = 0;
+                for (pos, (_, letter)) in grid.ray(start, dir).enumerate() {
+                    match self.nodes[node].children.get(letter) {
+                        Some(&child) => node = child,
+                        None => break,
+                    }
+                    let Some(word) = self.nodes[node].word else {
+                        continue;
+                    };
+                    if pos > 0 || i == 0 {
+                        hits.push(Hit {
+                            word: &self.words[word],
+                            start,
+                            dir,
+                        });
+                    }
+                }
+            }
+        }
+        hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    fn grid(input: &str) -> Grid<u8> {
+        Grid::parse(input, "letter", |c| Some(c as u8)).unwrap()
+    }
+
+    #[test]
+    fn test_shared_prefixes() {
+        let grid = grid("CAT\nARE\nRED");
+        let search = WordSearch::new(&["CAT", "CAR", "CA", "RED", "DOG"]);
+        let hits = search.find_all(&grid);
+        let expected = vec![
+            Hit {
+                word: "CA",
+                start: Point::new(0, 0),
+                dir: Direction::Right,
+            },
+            Hit {
+                word: "CAT",
+                start: Point::new(0, 0),
+                dir: Direction::Right,
+            },
+            Hit {
+                word: "CA",
+                start: Point::new(0, 0),
+                dir: Direction::Down,
+            },
+            Hit {
+                word: "CAR",
+                start: Point::new(0, 0),
+                dir: Direction::Down,
+            },
+            Hit {
+                word: "RED",
+                start: Point::new(2, 0),
+                dir: Direction::Right,
+            },
+        ];
+        assert_eq!(hits, expected);
+    }
+
+    #[test]
+    fn test_one_letter() {
+        let grid = grid("AB\nBA");
+        let search = WordSearch::new(&["A", "AB"]);
+        let hits: Vec<_> = search
+            .find_all(&grid)
+            .into_iter()
+            .map(|hit| (hit.word, hit.start))
+            .collect();
+        assert_eq!(
+            hits,
+            vec![
+                ("A", Point::new(0, 0)),
+                ("AB", Point::new(0, 0)),
+                ("AB", Point::new(0, 0)),
+                ("A", Point::new(1, 1)),
+                ("AB", Point::new(1, 1)),
+                ("AB", Point::new(1, 1)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use crate::{PageRules, Rule};
+
+// The pages and rules to draw, and for each rule whether the update breaks it.
+struct Drawing {
+    pages: Vec<i32>,
+    rules: Vec<(Rule, bool)>,
+}
+
+impl PageRules {
+    // Every rule, or with `update` only the rules between its pages, as a
+    // Graphviz digraph. Rules the update breaks are drawn in red.
+    pub fn to_dot(&self, update: Option<&[i32]>) -> String {
+        let drawing = self.drawing(update);
+        let mut out = String::from("digraph rules {\n");
+        for page in &drawing.pages {
+            out.push_str(&format!("    {};\n", page));
+        }
+        for (Rule(x, y), broken) in &drawing.rules {
+            let style = if *broken { " [color=red]" } else { "" };
+            out.push_str(&format!("    {} -> {}{};\n", x, y, style));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    // The same graph as a Mermaid flowchart.
+    pub fn to_mermaid(&self, update: Option<&[i32]>) -> String {
+        let drawing = self.drawing(update);
+        let mut out = String::from("flowchart LR\n");
+        for page in &drawing.pages {
+            out.push_str(&format!("    {}[{}]\n", node_id(*page), page));
+        }
+        for (Rule(x, y), _) in &drawing.rules {
+            out.push_str(&format!("    {} --> {}\n", node_id(*x), node_id(*y)));
+        }
+        // mermaid styles links by their position in the chart
+        for (i, _) in drawing.rules.iter().enumerate().filter(|(_, r)| r.1) {
+            out.push_str(&format!("    linkStyle {} stroke:red\n", i));
+        }
+        out
+    }
+
+    fn drawing(&self, update: Option<&[i32]>) -> Drawing {
+        let (pages, position): (Vec<i32>, HashMap<i32, usize>) = match update {
+            Some(pages) => (
+                pages.to_vec(),
+                pages.iter().enumerate().map(|(i, p)| (*p, i)).collect(),
+            ),
+            None => {
+                let mut pages: Vec<i32> = self
+                    .after
+                    .iter()
+                    .flat_map(|(x, ys)| ys.iter().chain([x]).copied())
+                    .collect();
+                pages.sort_unstable();
+                pages.dedup();
+                (pages, HashMap::new())
+            }
+        };
+        let mut rules = Vec::new();
+        for x in &pages {
+            let mut nexts: Vec<i32> = self.successors(*x).collect();
+            nexts.sort_unstable();
+            for y in nexts {
+                match (update, position.get(x), position.get(&y)) {
+                    (None, _, _) => rules.push((Rule(*x, y), false)),
+                    (Some(_), Some(i), Some(j)) => rules.push((Rule(*x, y), j < i)),
+                    // a rule about a page the update doesn't have
+                    _ => (),
+                }
+            }
+        }
+        Drawing { pages, rules }
+    }
+}
+
+// Mermaid ids can't have a minus sign, so -5 is pm5.
+fn node_id(page: i32) -> String {
+    if page < 0 {
+        format!("pm{}", page.unsigned_abs())
+    } else {
+        format!("p{}", page)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_export() {
+        let rules = PageRules::new(&[Rule(97, 13), Rule(13, 61), Rule(97, 5)]);
+        let update = [97, 61, 13];
+        assert_eq!(
+            rules.to_dot(Some(&update)),
+            "digraph rules {
+    97;
+    61;
+    13;
+    97 -> 13;
+    13 -> 61 [color=red];
+}
+"
+        );
+        assert_eq!(
+            rules.to_mermaid(Some(&update)),
+            "flowchart LR
+    p97[97]
+    p61[61]
+    p13[13]
+    p97 --> p13
+    p13 --> p61
+    linkStyle 1 stroke:red
+"
+        );
+        assert_eq!(rules.to_dot(None).lines().count(), 2 + 4 + 3);
+
+        let rules = PageRules::new(&[Rule(-5, 5)]);
+        assert_eq!(
+            rules.to_mermaid(None),
+            "flowchart LR\n    pm5[-5]\n    p5[5]\n    pm5 --> p5\n"
+        );
+    }
+}
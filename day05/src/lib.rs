@@ -0,0 +1,601 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+    error::Error,
+    fmt,
+};
+
+use common::{lines, Answer, ParseError, Solution};
+
+mod export;
+
+// `Rule(x, y)`: page x has to be printed before page y.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule(pub i32, pub i32);
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}|{}", self.0, self.1)
+    }
+}
+
+// Rules that contradict each other within one update: each page of the
+// cycle has to come before the next, and the last before the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError {
+    pub pages: Vec<i32>,
+    pub rules: Vec<Rule>,
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rules: Vec<_> = self.rules.iter().map(Rule::to_string).collect();
+        write!(f, "the rules {} form a cycle", rules.join(", "))
+    }
+}
+
+impl Error for CycleError {}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Manual;
+
+    fn parse(&self, input: &str) -> Result<Manual, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, manual: &Manual) -> Answer {
+        part1(manual).into()
+    }
+
+    fn part2(&self, manual: &Manual) -> Answer {
+        match part2(manual) {
+            Ok(sum) => sum.into(),
+            Err((i, e)) => format!("none, update {} can't be ordered: {}", i + 1, e).into(),
+        }
+    }
+}
+
+pub struct Manual {
+    pub rules: PageRules,
+    pub orders: Vec<Vec<i32>>,
+}
+
+fn parse(input: &str) -> Result<Manual, ParseError> {
+    let mut lines = lines(input);
+    let mut rules_vec = Vec::new();
+    // rules run up to the first blank line, the updates follow it
+    for mut line in lines.by_ref() {
+        if line.at_end() {
+            break;
+        }
+        let before = line.number()?;
+        line.expect("|")?;
+        let after = line.number()?;
+        line.end()?;
+        rules_vec.push(Rule(before, after));
+    }
+    let rules = PageRules::new(&rules_vec);
+    let orders_vec = lines
+        .map(|mut line| {
+            let mut pages = vec![line.number()?];
+            while !line.at_end() {
+                line.expect(",")?;
+                pages.push(line.number()?);
+            }
+            Ok(pages)
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Manual {
+        rules,
+        orders: orders_vec,
+    })
+}
+
+impl Manual {
+    // The updates whose rules form a cycle, so that they can't be put in
+    // order, by index into `orders`. Parsing doesn't turn them away, so the
+    // rest of the manual can still be answered and drawn.
+    pub fn validate(&self) -> Vec<(usize, CycleError)> {
+        self.orders
+            .iter()
+            .enumerate()
+            .filter_map(|(i, pages)| self.rules.check(pages).err().map(|e| (i, e)))
+            .collect()
+    }
+}
+
+fn part1(manual: &Manual) -> i32 {
+    let rules = &manual.rules;
+    let result: i32 = manual
+        .orders
+        .iter()
+        .filter_map(|v| {
+            let valid = rules.is_valid_order(v);
+            if valid {
+                //find middle number
+                let size = v.len();
+                Some(v[size / 2])
+            } else {
+                None
+            }
+        })
+        .sum();
+    result
+}
+
+// Fails on the first update whose rules form a cycle, with its index, as
+// there is no right order to take the middle page of.
+fn part2(manual: &Manual) -> Result<i32, (usize, CycleError)> {
+    let rules = &manual.rules;
+    let mut result = 0;
+    for (i, v) in manual.orders.iter().enumerate() {
+        let valid = rules.is_valid_order(v);
+        if !valid {
+            //fix the order and find the middle page
+            let new_order = rules.fix_order(v).map_err(|e| (i, e))?;
+            let size = new_order.len();
+            result += new_order[size / 2];
+        }
+    }
+    Ok(result)
+}
+
+// A fixed update, and the fewest pages that had to move to get there.
+// `moved` lists them in the order they had in the update.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Correction {
+    pub order: Vec<i32>,
+    pub moved: Vec<i32>,
+}
+
+// The rule graph, asked about one update at a time.
+//
+// Only the rules between pages of the update being looked at matter, so
+// every question is answered on the subgraph those pages induce. The rule
+// set as a whole doesn't have to be a partial order (the puzzle's isn't,
+// its rules go round in circles) as long as each update's share of it is.
+//
+// Nothing is derived from the rules as a whole, so adding or retracting a
+// rule is a single set operation and the next question simply sees it.
+#[derive(Debug, Clone, Default)]
+pub struct PageRules {
+    // page -> every page that has to come after it
+    after: HashMap<i32, HashSet<i32>>,
+}
+
+impl PageRules {
+    pub fn new(rules: &[Rule]) -> PageRules {
+        let mut page_rules = PageRules::default();
+        for rule in rules {
+            page_rules.add_rule(*rule);
+        }
+        page_rules
+    }
+
+    // Returns false if the rule was already there.
+    pub fn add_rule(&mut self, Rule(x, y): Rule) -> bool {
+        self.after.entry(x).or_default().insert(y)
+    }
+
+    // Returns false if there was no such rule.
+    pub fn retract_rule(&mut self, Rule(x, y): Rule) -> bool {
+        let Some(nexts) = self.after.get_mut(&x) else {
+            return false;
+        };
+        let removed = nexts.remove(&y);
+        if nexts.is_empty() {
+            self.after.remove(&x);
+        }
+        removed
+    }
+
+    pub fn rules(&self) -> impl Iterator<Item = Rule> + '_ {
+        self.after
+            .iter()
+            .flat_map(|(x, ys)| ys.iter().map(|y| Rule(*x, *y)))
+    }
+
+    fn successors(&self, page: i32) -> impl Iterator<Item = i32> + '_ {
+        self.after.get(&page).into_iter().flatten().copied()
+    }
+
+    // True when no rule puts a later page of the update before an earlier one.
+    pub fn is_valid_order(&self, pages: &[i32]) -> bool {
+        let position: HashMap<i32, usize> =
+            pages.iter().enumerate().map(|(i, p)| (*p, i)).collect();
+        pages.iter().enumerate().all(|(i, page)| {
+            self.successors(*page)
+                .all(|next| position.get(&next).is_none_or(|&j| j > i))
+        })
+    }
+
+    // Fails if the rules between the update's pages contain a cycle.
+    pub fn check(&self, pages: &[i32]) -> Result<(), CycleError> {
+        self.fix_order(pages).map(|_| ())
+    }
+
+    // The update's pages in an order that follows every rule between them,
+    // by topologically sorting the induced subgraph. Of the pages that are
+    // free to go next, the one that came first in the update goes first, so
+    // pages the rules don't constrain keep their relative order.
+    pub fn fix_order(&self, pages: &[i32]) -> Result<Vec<i32>, CycleError> {
+        topo_sort(pages, &self.induced_edges(pages))
+    }
+
+    // Fixes the update with as few pages moved as possible. The pages that
+    // stay put keep their order, so no later one of them may have to come
+    // before an earlier one, directly or through other pages of the update.
+    // Such a set is an antichain of the partial order "a later page that has
+    // to come before an earlier one", and a largest one is found from a
+    // maximum matching (Dilworth's theorem, via Kőnig's). The fixed order
+    // is then a topological sort that keeps those pages in line.
+    pub fn correct(&self, pages: &[i32]) -> Result<Correction, CycleError> {
+        let mut edges = self.induced_edges(pages);
+        topo_sort(pages, &edges)?;
+        let stays = stays_put(&edges);
+        let kept: Vec<usize> = (0..pages.len()).filter(|i| stays[*i]).collect();
+        for pair in kept.windows(2) {
+            edges[pair[0]].push(pair[1]);
+        }
+        let order = topo_sort(pages, &edges).expect("the pages that stay can't form a cycle");
+        let moved = (0..pages.len())
+            .filter(|i| !stays[*i])
+            .map(|i| pages[i])
+            .collect();
+        Ok(Correction { order, moved })
+    }
+
+    // For every page of the update, the positions of the pages that have
+    // to come after it.
+    fn induced_edges(&self, pages: &[i32]) -> Vec<Vec<usize>> {
+        let position: HashMap<i32, usize> =
+            pages.iter().enumerate().map(|(i, p)| (*p, i)).collect();
+        pages
+            .iter()
+            .map(|page| {
+                self.successors(*page)
+                    .filter_map(|next| position.get(&next).copied())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+// Kahn's algorithm, with a min-heap on position in the update.
+fn topo_sort(pages: &[i32], edges: &[Vec<usize>]) -> Result<Vec<i32>, CycleError> {
+    let mut incoming = vec![0; pages.len()];
+    for next in edges.iter().flatten() {
+        incoming[*next] += 1;
+    }
+    let mut ready: BinaryHeap<_> = (0..pages.len())
+        .filter(|i| incoming[*i] == 0)
+        .map(Reverse)
+        .collect();
+    let mut order = Vec::with_capacity(pages.len());
+    while let Some(Reverse(i)) = ready.pop() {
+        order.push(pages[i]);
+        for &next in &edges[i] {
+            incoming[next] -= 1;
+            if incoming[next] == 0 {
+                ready.push(Reverse(next));
+            }
+        }
+    }
+    if order.len() < pages.len() {
+        return Err(find_cycle(pages, edges, &incoming));
+    }
+    Ok(order)
+}
+
+// Which pages can stay where they are, for `correct`. The rules between the
+// pages mustn't have a cycle.
+fn stays_put(edges: &[Vec<usize>]) -> Vec<bool> {
+    let n = edges.len();
+    let reach: Vec<Vec<bool>> = (0..n).map(|i| reachable(edges, i)).collect();
+    // behind[i]: the later pages that have to come before page i
+    let behind: Vec<Vec<usize>> = (0..n)
+        .map(|i| (i + 1..n).filter(|&j| reach[j][i]).collect())
+        .collect();
+    // partner[j]: the page matched with later page j
+    let mut partner = vec![None; n];
+    for i in 0..n {
+        augment(i, &behind, &mut partner, &mut vec![false; n]);
+    }
+    // alternating paths from every unmatched page
+    let mut left = vec![true; n];
+    for i in partner.iter().flatten() {
+        left[*i] = false;
+    }
+    let mut right = vec![false; n];
+    let mut stack: Vec<usize> = (0..n).filter(|i| left[*i]).collect();
+    while let Some(i) = stack.pop() {
+        for &j in &behind[i] {
+            if right[j] {
+                continue;
+            }
+            right[j] = true;
+            if let Some(k) = partner[j] {
+                if !left[k] {
+                    left[k] = true;
+                    stack.push(k);
+                }
+            }
+        }
+    }
+    (0..n).map(|i| left[i] && !right[i]).collect()
+}
+
+// Looks for an augmenting path from page i (Kuhn's algorithm).
+fn augment(
+    i: usize,
+    behind: &[Vec<usize>],
+    partner: &mut [Option<usize>],
+    seen: &mut [bool],
+) -> bool {
+    for &j in &behind[i] {
+        if seen[j] {
+            continue;
+        }
+        seen[j] = true;
+        if partner[j].is_none_or(|k| augment(k, behind, partner, seen)) {
+            partner[j] = Some(i);
+            return true;
+        }
+    }
+    false
+}
+
+// The pages that have to come after page `start`, directly or not.
+fn reachable(edges: &[Vec<usize>], start: usize) -> Vec<bool> {
+    let mut seen = vec![false; edges.len()];
+    let mut stack = vec![start];
+    while let Some(i) = stack.pop() {
+        for &next in &edges[i] {
+            if !seen[next] {
+                seen[next] = true;
+                stack.push(next);
+            }
+        }
+    }
+    seen
+}
+
+// Every page left over by the sort still has a rule coming in from another
+// left over page, so walking those rules backwards has to come round to a
+// page it has already seen.
+fn find_cycle(pages: &[i32], edges: &[Vec<usize>], incoming: &[usize]) -> CycleError {
+    let stuck = |i: usize| incoming[i] > 0;
+    let mut before = vec![None; pages.len()];
+    for (i, nexts) in edges.iter().enumerate().filter(|(i, _)| stuck(*i)) {
+        for &next in nexts.iter().filter(|next| stuck(**next)) {
+            before[next] = Some(i);
+        }
+    }
+    let mut seen = vec![false; pages.len()];
+    let mut i = (0..pages.len()).find(|i| stuck(*i)).unwrap();
+    while !seen[i] {
+        seen[i] = true;
+        i = before[i].unwrap();
+    }
+    // i is on the cycle, follow it round once
+    let mut cycle = vec![i];
+    let mut j = before[i].unwrap();
+    while j != i {
+        cycle.push(j);
+        j = before[j].unwrap();
+    }
+    cycle.reverse();
+    // start from whichever page comes first in the update
+    let first = (0..cycle.len()).min_by_key(|k| cycle[*k]).unwrap();
+    cycle.rotate_left(first);
+    let pages: Vec<i32> = cycle.iter().map(|i| pages[*i]).collect();
+    let rules = (0..pages.len())
+        .map(|k| Rule(pages[k], pages[(k + 1) % pages.len()]))
+        .collect();
+    CycleError { pages, rules }
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = "47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47";
+        let answer = part1(&parse(input).unwrap());
+        assert_eq!(answer, 143);
+    }
+
+    fn logic(rules: &[Rule], list: &[i32]) -> bool {
+        PageRules::new(rules).is_valid_order(list)
+    }
+
+    #[test]
+    fn test_simple() {
+        let result = logic(&[Rule(97, 13)], &[97, 13]);
+        assert!(result);
+    }
+
+    #[test]
+    fn test_simple_bad() {
+        let result = logic(&[Rule(97, 13)], &[13, 97]);
+        assert!(!result);
+    }
+
+    #[test]
+    fn test_slightly_simple() {
+        let result = logic(&[Rule(97, 13), Rule(13, 61)], &[97, 13, 61]);
+        assert!(result);
+    }
+
+    #[test]
+    fn test_slightly_simple_bad() {
+        let result = logic(&[Rule(97, 13), Rule(13, 61)], &[97, 61, 13]);
+        assert!(!result);
+    }
+
+    #[test]
+    fn test_cyclic_rules() {
+        // a cycle overall, but any two of the three pages can be ordered
+        let rules = PageRules::new(&[Rule(1, 2), Rule(2, 3), Rule(3, 1)]);
+        assert!(rules.is_valid_order(&[1, 2]));
+        assert!(rules.is_valid_order(&[3, 1]));
+        assert_eq!(rules.fix_order(&[2, 1]), Ok(vec![1, 2]));
+        assert_eq!(rules.fix_order(&[1, 3]), Ok(vec![3, 1]));
+    }
+
+    #[test]
+    fn test_correct() {
+        let rules: Vec<_> = (1..=6)
+            .flat_map(|x| (x + 1..=6).map(move |y| Rule(x, y)))
+            .collect();
+        let rules = PageRules::new(&rules);
+        let correction = rules.correct(&[2, 6, 3, 1, 4, 5]).unwrap();
+        assert_eq!(correction.order, vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(correction.moved, vec![6, 1]);
+        assert!(rules.correct(&[1, 2, 3]).unwrap().moved.is_empty());
+
+        // 3 isn't ordered against the others, so moving 4 alone will do
+        let rules = PageRules::new(&[Rule(4, 1), Rule(1, 2)]);
+        let correction = rules.correct(&[1, 2, 3, 4]).unwrap();
+        assert_eq!(correction.order, vec![4, 1, 2, 3]);
+        assert_eq!(correction.moved, vec![4]);
+
+        // only one of 1 and 3 has to move, whichever it is
+        let rules = PageRules::new(&[Rule(3, 1)]);
+        assert_eq!(rules.correct(&[1, 2, 3]).unwrap().moved.len(), 1);
+    }
+
+    #[test]
+    fn test_add_and_retract() {
+        let mut rules = PageRules::new(&[Rule(97, 13), Rule(13, 61)]);
+        assert!(rules.is_valid_order(&[97, 13, 61]));
+        assert!(rules.add_rule(Rule(61, 97)));
+        assert!(!rules.add_rule(Rule(61, 97)));
+        assert!(rules.check(&[97, 13, 61]).is_err());
+
+        assert!(rules.retract_rule(Rule(13, 61)));
+        assert!(!rules.retract_rule(Rule(13, 61)));
+        assert_eq!(rules.fix_order(&[97, 13, 61]), Ok(vec![61, 97, 13]));
+        assert_eq!(rules.rules().count(), 2);
+    }
+
+    #[test]
+    fn test_rule_added_after_parse() {
+        let mut manual = parse("1|2\n2|3\n\n4,5,6\n3,2,1").unwrap();
+        assert_eq!(part2(&manual), Ok(2));
+        manual.rules.add_rule(Rule(6, 5));
+        assert_eq!(part2(&manual), Ok(8));
+        manual.rules.add_rule(Rule(3, 1));
+        let cycle = CycleError {
+            pages: vec![3, 1, 2],
+            rules: vec![Rule(3, 1), Rule(1, 2), Rule(2, 3)],
+        };
+        assert_eq!(part2(&manual), Err((1, cycle)));
+        assert_eq!(
+            Day05.part2(&manual).to_string(),
+            "none, update 2 can't be ordered: the rules 3|1, 1|2, 2|3 form a cycle"
+        );
+    }
+
+    #[test]
+    fn test_cycle_error() {
+        let rules = PageRules::new(&[Rule(1, 2), Rule(2, 3), Rule(3, 1), Rule(4, 1)]);
+        assert_eq!(
+            rules.fix_order(&[4, 3, 2, 1]),
+            Err(CycleError {
+                pages: vec![3, 1, 2],
+                rules: vec![Rule(3, 1), Rule(1, 2), Rule(2, 3)],
+            })
+        );
+
+        let manual = parse("1|2\n2|3\n3|1\n\n1,2\n1,2,3").unwrap();
+        assert_eq!(
+            manual.validate(),
+            vec![(
+                1,
+                CycleError {
+                    pages: vec![1, 2, 3],
+                    rules: vec![Rule(1, 2), Rule(2, 3), Rule(3, 1)],
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47";
+        let answer = part2(&parse(input).unwrap());
+        assert_eq!(answer, Ok(123));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "47|53
+97|
+
+75,47,61";
+        let result = parse(input).err().map(|e| e.to_string());
+        assert_eq!(
+            result.as_deref(),
+            Some("line 2, column 4: expected number, found end of line")
+        );
+    }
+}
@@ -0,0 +1,295 @@
+use std::thread;
+
+use common::{Answer, ParseError, Solution};
+use grid::{Direction, Grid, Point};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Lab;
+
+    fn parse(&self, input: &str) -> Result<Lab, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, lab: &Lab) -> Answer {
+        part1(lab).into()
+    }
+
+    fn part2(&self, lab: &Lab) -> Answer {
+        part2(lab).into()
+    }
+}
+
+type Matrix = Grid<Space>;
+
+pub struct Lab {
+    matrix: Matrix,
+    guard: Guard,
+}
+
+fn part1(lab: &Lab) -> usize {
+    let mut visits = Visits::new(&lab.matrix);
+    lab.guard.clone().patrol(&lab.matrix, None, &mut visits);
+    visits.count()
+}
+
+fn parse(input: &str) -> Result<Lab, ParseError> {
+    let map = Grid::parse(input, "'.', '#' or '^'", |c| {
+        matches!(c, '.' | '#' | '^').then_some(c)
+    })?;
+    let location = map
+        .iter()
+        .find_map(|(p, c)| (*c == '^').then_some(p))
+        .ok_or_else(|| ParseError::new(map.height(), map.width() + 1, "", "guard '^'"))?;
+    let matrix = map.map(|c| match c {
+        '#' => Space::Obstruction,
+        _ => Space::Empty,
+    });
+    let guard = Guard {
+        location,
+        direction: Direction::Up,
+    };
+    Ok(Lab { matrix, guard })
+}
+
+fn part2(lab: &Lab) -> usize {
+    loop_positions(lab).len()
+}
+
+// Every cell where one more obstruction traps the guard in a loop, in the
+// order the guard first walks into them. Only cells on the original patrol
+// can change it, and each is tried starting from the step before the guard
+// first reaches it, with the candidates shared out between threads.
+pub fn loop_positions(lab: &Lab) -> Vec<Point> {
+    let Lab { matrix, guard } = lab;
+    let mut visits = Visits::new(matrix);
+    let mut guard = guard.clone();
+    visits.visit(guard.location, guard.direction);
+    let mut candidates = Vec::new();
+    loop {
+        let before = guard.clone();
+        let seen = visits.count();
+        if !matches!(guard.action(matrix, None, &mut visits), Status::Continue) {
+            break;
+        }
+        if visits.count() > seen {
+            candidates.push((guard.location, before));
+        }
+    }
+
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = candidates.len().div_ceil(threads).max(1);
+    thread::scope(|scope| {
+        let handles: Vec<_> = candidates
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    let mut visits = Visits::new(matrix);
+                    let mut found = Vec::new();
+                    for (p, guard) in chunk {
+                        visits.clear();
+                        let status = guard.clone().patrol(matrix, Some(*p), &mut visits);
+                        if let Status::Looped = status {
+                            found.push(*p);
+                        }
+                    }
+                    found
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
+
+#[derive(Clone)]
+struct Guard {
+    location: Point,
+    direction: Direction,
+}
+
+enum Status {
+    Continue,
+    Looped,
+    Exited,
+}
+
+impl Guard {
+    // Walks until the guard leaves the lab or comes back to a cell facing a
+    // way it has faced there before. `extra` is one more obstruction.
+    fn patrol(&mut self, matrix: &Matrix, extra: Option<Point>, visits: &mut Visits) -> Status {
+        visits.visit(self.location, self.direction);
+        loop {
+            match self.action(matrix, extra, visits) {
+                Status::Continue => (),
+                status => return status,
+            }
+        }
+    }
+
+    fn action(&mut self, matrix: &Matrix, extra: Option<Point>, visits: &mut Visits) -> Status {
+        loop {
+            let Some(next) = matrix.step(self.location, self.direction) else {
+                return Status::Exited;
+            };
+            if let Space::Obstruction = matrix[next] {
+                self.direction = self.direction.turn_right();
+            } else if extra == Some(next) {
+                self.direction = self.direction.turn_right();
+            } else {
+                self.location = next;
+                if !visits.visit(next, self.direction) {
+                    return Status::Looped;
+                }
+                break;
+            }
+        }
+        Status::Continue
+    }
+}
+
+#[derive(Clone)]
+enum Space {
+    Empty,
+    Obstruction,
+}
+
+// Which ways the guard has walked through each cell, one bit for each of
+// the four directions. The cells touched are remembered so clearing only
+// has to visit those.
+struct Visits {
+    masks: Grid<u8>,
+    touched: Vec<Point>,
+}
+
+impl Visits {
+    fn new(matrix: &Matrix) -> Visits {
+        Visits {
+            masks: Grid::new(matrix.width(), matrix.height(), 0),
+            touched: Vec::new(),
+        }
+    }
+
+    fn bit(direction: Direction) -> u8 {
+        match direction {
+            Direction::Up => 1,
+            Direction::Right => 2,
+            Direction::Down => 4,
+            Direction::Left => 8,
+            _ => unreachable!("the guard only turns right angles"),
+        }
+    }
+
+    // Returns false if the guard has been through here going this way before.
+    fn visit(&mut self, p: Point, direction: Direction) -> bool {
+        let mask = &mut self.masks[p];
+        if *mask == 0 {
+            self.touched.push(p);
+        }
+        let bit = Visits::bit(direction);
+        if *mask & bit != 0 {
+            return false;
+        }
+        *mask |= bit;
+        true
+    }
+
+    // distinct cells visited
+    fn count(&self) -> usize {
+        self.touched.len()
+    }
+
+    fn clear(&mut self) {
+        for p in self.touched.drain(..) {
+            self.masks[p] = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+        let answer = part1(&parse(input).unwrap());
+        assert_eq!(answer, 41);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+        let answer = part2(&parse(input).unwrap());
+        assert_eq!(answer, 6);
+    }
+
+    #[test]
+    fn test_loop_positions() {
+        let input = "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+        let mut positions = loop_positions(&parse(input).unwrap());
+        positions.sort_by_key(|p| (p.y, p.x));
+        let expected = [(6, 3), (7, 6), (7, 7), (8, 1), (8, 3), (9, 7)];
+        let expected: Vec<Point> = expected.iter().map(|&(y, x)| Point { y, x }).collect();
+        assert_eq!(positions, expected);
+    }
+
+    #[test]
+    fn test_visits_clear() {
+        let lab = parse("#..\n..#\n^..").unwrap();
+        let mut visits = Visits::new(&lab.matrix);
+        let status = lab.guard.clone().patrol(&lab.matrix, None, &mut visits);
+        assert!(matches!(status, Status::Exited));
+        assert_eq!(visits.count(), 4);
+
+        // blocked straight away, so the guard heads right along the bottom
+        visits.clear();
+        assert!(visits.masks.iter().all(|(_, mask)| *mask == 0));
+        let status = lab
+            .guard
+            .clone()
+            .patrol(&lab.matrix, Some(Point { y: 1, x: 0 }), &mut visits);
+        assert!(matches!(status, Status::Exited));
+        assert_eq!(visits.count(), 3);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let result = parse("..#\n.^.\n.o.").err();
+        assert_eq!(result, Some(ParseError::new(3, 2, "o", "'.', '#' or '^'")));
+
+        let result = parse("..#\n...").err();
+        assert_eq!(result, Some(ParseError::new(2, 4, "", "guard '^'")));
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::VecDeque;
+
+use common::{lines, Answer, ParseError, Solution};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<Equation>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Equation>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, equations: &Vec<Equation>) -> Answer {
+        part1(equations).into()
+    }
+
+    fn part2(&self, equations: &Vec<Equation>) -> Answer {
+        part2(equations).into()
+    }
+}
+
+pub struct Equation {
+    pub test: i64,
+    pub nums: Vec<i64>,
+}
+
+fn part1(equations: &[Equation]) -> i64 {
+    let ops = [std::ops::Add::add, std::ops::Mul::mul];
+    sum_ops(equations, &ops)
+}
+
+fn concat(lhs: i64, rhs: i64) -> i64 {
+    let s = format!("{}{}", lhs, rhs);
+    s.parse().unwrap()
+}
+
+fn part2(equations: &[Equation]) -> i64 {
+    let ops = [std::ops::Add::add, std::ops::Mul::mul, concat];
+    sum_ops(equations, &ops)
+}
+
+fn parse(input: &str) -> Result<Vec<Equation>, ParseError> {
+    lines(input)
+        .map(|mut line| {
+            let test = line.number()?;
+            line.expect(": ")?;
+            let mut nums = vec![line.number()?];
+            while !line.at_end() {
+                line.expect(" ")?;
+                nums.push(line.number()?);
+            }
+            Ok(Equation { test, nums })
+        })
+        .collect()
+}
+
+fn sum_ops(equations: &[Equation], ops: &[fn(i64, i64) -> i64]) -> i64 {
+    equations
+        .iter()
+        .flat_map(|Equation { test, nums }| {
+            if valid_ops_exist(*test, nums, ops) {
+                Some(*test)
+            } else {
+                None
+            }
+        })
+        .sum()
+}
+
+fn valid_ops_exist(test: i64, nums: &[i64], ops: &[fn(i64, i64) -> i64]) -> bool {
+    let mut queue = VecDeque::new();
+    queue.push_back(nums[0]);
+    for rhs in &nums[1..] {
+        let mut new_queue = VecDeque::new();
+        while let Some(lhs) = queue.pop_front() {
+            for num in ops.iter().map(|f| f(lhs, *rhs)) {
+                // if num is greater than test, then we can prune this branch from the search tree
+                if num <= test {
+                    new_queue.push_back(num);
+                }
+            }
+        }
+        queue = new_queue;
+    }
+    for n in queue {
+        if n == test {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20";
+        let answer = part1(&parse(input).unwrap());
+        assert_eq!(answer, 3749);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20";
+        let answer = part2(&parse(input).unwrap());
+        assert_eq!(answer, 11387);
+    }
+}
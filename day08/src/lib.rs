@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use common::{Answer, ParseError, Solution};
+use grid::{Grid, Point};
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Grid<AntSpace>;
+
+    fn parse(&self, input: &str) -> Result<Grid<AntSpace>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, ant_matrix: &Grid<AntSpace>) -> Answer {
+        part1(ant_matrix).into()
+    }
+
+    fn part2(&self, ant_matrix: &Grid<AntSpace>) -> Answer {
+        part2(ant_matrix).into()
+    }
+}
+
+fn part1(ant_matrix: &Grid<AntSpace>) -> usize {
+    let mut node_matrix = empty_nodes(ant_matrix);
+    let ant_map = map_ants(ant_matrix);
+    for antenas in ant_map.values() {
+        generate_nodes(&mut node_matrix, antenas, insert_nodes_in_direction);
+    }
+    count_nodes(&node_matrix)
+}
+
+fn parse(input: &str) -> Result<Grid<AntSpace>, ParseError> {
+    Grid::parse(input, "'.' or an antenna", |c| match c {
+        '.' => Some(AntSpace::Empty),
+        _ if c.is_ascii_alphanumeric() => Some(AntSpace::Antena(c)),
+        _ => None,
+    })
+}
+
+fn empty_nodes(ant_matrix: &Grid<AntSpace>) -> Grid<NodeSpace> {
+    ant_matrix.map(|_| NodeSpace::Empty)
+}
+
+fn part2(ant_matrix: &Grid<AntSpace>) -> usize {
+    let mut node_matrix = empty_nodes(ant_matrix);
+    let ant_map = map_ants(ant_matrix);
+    for antenas in ant_map.values() {
+        generate_nodes(&mut node_matrix, antenas, insert_res_nodes_in_direction);
+    }
+    count_nodes(&node_matrix)
+}
+
+fn map_ants(matrix: &Grid<AntSpace>) -> HashMap<char, Vec<Point>> {
+    let mut map: HashMap<char, Vec<_>> = HashMap::new();
+    for (p, space) in matrix.iter() {
+        if let AntSpace::Antena(freq) = space {
+            map.entry(*freq)
+                .and_modify(|v| v.push(p))
+                .or_insert_with(|| vec![p]);
+        }
+    }
+    map
+}
+
+type InsertFn = fn(&mut Grid<NodeSpace>, Point, isize, isize);
+
+fn generate_nodes(matrix: &mut Grid<NodeSpace>, antenas: &[Point], insert: InsertFn) {
+    if antenas.len() < 2 {
+        return;
+    }
+    for i in 0..antenas.len() {
+        for j in i + 1..antenas.len() {
+            let ant1 = antenas[i];
+            let ant2 = antenas[j];
+            let diffy = ant1.y as isize - ant2.y as isize;
+            let diffx = ant1.x as isize - ant2.x as isize;
+            insert(matrix, ant1, diffy, diffx);
+            insert(matrix, ant2, -diffy, -diffx);
+        }
+    }
+}
+
+fn insert_nodes_in_direction(
+    matrix: &mut Grid<NodeSpace>,
+    start: Point,
+    offsety: isize,
+    offsetx: isize,
+) {
+    if let Some(p) = matrix.offset(start, offsety, offsetx) {
+        matrix[p] = NodeSpace::Node;
+    }
+}
+
+fn insert_res_nodes_in_direction(
+    matrix: &mut Grid<NodeSpace>,
+    start: Point,
+    offsety: isize,
+    offsetx: isize,
+) {
+    let mut next = Some(start);
+    while let Some(p) = next {
+        matrix[p] = NodeSpace::Node;
+        next = matrix.offset(p, offsety, offsetx);
+    }
+}
+
+fn count_nodes(matrix: &Grid<NodeSpace>) -> usize {
+    matrix
+        .iter()
+        .filter(|(_, space)| match space {
+            NodeSpace::Empty => false,
+            NodeSpace::Node => true,
+        })
+        .count()
+}
+
+#[derive(Debug)]
+pub enum AntSpace {
+    Empty,
+    Antena(char),
+}
+
+#[derive(Debug)]
+enum NodeSpace {
+    Empty,
+    Node,
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = "............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............";
+        let answer = part1(&parse(input).unwrap());
+        assert_eq!(answer, 14);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............";
+        let answer = part2(&parse(input).unwrap());
+        assert_eq!(answer, 34);
+    }
+}
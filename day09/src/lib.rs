@@ -0,0 +1,168 @@
+use common::{lines, Answer, ParseError, Solution};
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<Option<usize>>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Option<usize>>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, disk: &Vec<Option<usize>>) -> Answer {
+        part1(disk).into()
+    }
+
+    fn part2(&self, disk: &Vec<Option<usize>>) -> Answer {
+        part2(disk).into()
+    }
+}
+
+fn part1(disk: &[Option<usize>]) -> usize {
+    let mut disk = disk.to_vec();
+    defrag(&mut disk);
+    checksum(&disk)
+}
+
+fn part2(disk: &[Option<usize>]) -> usize {
+    let mut disk = disk.to_vec();
+    whole_file_defrag(&mut disk);
+    checksum(&disk)
+}
+
+fn parse(input: &str) -> Result<Vec<Option<usize>>, ParseError> {
+    let mut lines = lines(input);
+    let mut line = lines
+        .next()
+        .ok_or_else(|| ParseError::new(1, 1, "", "digit"))?;
+    if let Some(extra) = lines.next() {
+        return Err(extra.error("end of input"));
+    }
+    let mut disk = Vec::new();
+    let mut i = 0;
+    while !line.at_end() {
+        let blocks = line.digit()?;
+        let val = if i % 2 == 0 {
+            let id = i / 2;
+            Some(id)
+        } else {
+            None
+        };
+
+        for _ in 0..blocks {
+            disk.push(val);
+        }
+        i += 1;
+    }
+    // both ways of compacting start from the last file block
+    if disk.iter().all(Option::is_none) {
+        return Err(line.error("a file with at least one block"));
+    }
+    Ok(disk)
+}
+
+fn defrag(disk: &mut [Option<usize>]) {
+    let mut start = 0;
+    let mut end = disk.len() - 1;
+    while start < end {
+        if disk[end].is_some() {
+            if disk[start].is_none() {
+                disk.swap(start, end);
+            } else {
+                start += 1;
+            }
+        } else {
+            end -= 1;
+        }
+    }
+}
+
+fn whole_file_defrag(disk: &mut [Option<usize>]) {
+    let mut last_data_idx = disk.len() - 1;
+    while disk[last_data_idx].is_none() {
+        last_data_idx -= 1;
+    }
+    let mut id = disk[last_data_idx].unwrap();
+    while id > 0 {
+        let (fstart, fend) = find_file(disk, id, last_data_idx);
+        last_data_idx = fend;
+        let size = fend - fstart + 1;
+        if let Some((estart, eend)) = find_empty(disk, size, fstart) {
+            for (i, j) in (fstart..=fend).zip(estart..=eend) {
+                disk.swap(i, j);
+            }
+        }
+        id -= 1;
+    }
+}
+
+fn find_file(disk: &[Option<usize>], id: usize, hint: usize) -> (usize, usize) {
+    let mut start = 0;
+    let mut end = 0;
+    let mut found = false;
+
+    for i in (0..=hint).rev() {
+        if let Some(val) = disk[i] {
+            if val == id {
+                if !found {
+                    found = true;
+                    end = i;
+                }
+            } else if found {
+                start = i + 1;
+                break;
+            }
+        } else if found {
+            start = i + 1;
+            break;
+        }
+    }
+    (start, end)
+}
+
+fn find_empty(disk: &[Option<usize>], size: usize, before_idx: usize) -> Option<(usize, usize)> {
+    for i in 0..before_idx {
+        if disk.iter().skip(i).take(size).all(Option::is_none) {
+            return Some((i, i + size - 1));
+        }
+    }
+    None
+}
+
+fn checksum(disk: &[Option<usize>]) -> usize {
+    disk.iter()
+        .enumerate()
+        .filter_map(|(i, val)| val.map(|num| num * i))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = "2333133121414131402";
+        let answer = part1(&parse(input).unwrap());
+        assert_eq!(answer, 1928);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "2333133121414131402";
+        let answer = part2(&parse(input).unwrap());
+        assert_eq!(answer, 2858);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let result = parse("09").err();
+        assert_eq!(
+            result,
+            Some(ParseError::new(1, 3, "", "a file with at least one block"))
+        );
+        assert!(parse("0").is_err());
+        assert_eq!(part2(&parse("101").unwrap()), 1);
+    }
+}
@@ -0,0 +1,143 @@
+use common::{Answer, ParseError, Solution};
+use grid::Grid;
+use petgraph::{
+    algo::{all_simple_paths, has_path_connecting},
+    graph::DiGraph,
+};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Grid<i32>;
+
+    fn parse(&self, input: &str) -> Result<Grid<i32>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, matrix: &Grid<i32>) -> Answer {
+        part1(matrix).into()
+    }
+
+    fn part2(&self, matrix: &Grid<i32>) -> Answer {
+        part2(matrix).into()
+    }
+}
+
+fn part1(matrix: &Grid<i32>) -> usize {
+    let (graph, trailheads, trailends) = build_graph(matrix);
+    count_scores(
+        &trailheads,
+        &trailends,
+        &graph,
+        |graph, head_idx, end_idx| {
+            if has_path_connecting(&graph, *head_idx, *end_idx, None) {
+                1
+            } else {
+                0
+            }
+        },
+    )
+}
+
+fn part2(matrix: &Grid<i32>) -> usize {
+    let (graph, trailheads, trailends) = build_graph(matrix);
+    count_scores(
+        &trailheads,
+        &trailends,
+        &graph,
+        |graph, head_idx, end_idx| {
+            all_simple_paths::<Vec<_>, _>(graph, *head_idx, *end_idx, 0, None).count()
+        },
+    )
+}
+
+fn count_scores(
+    trailheads: &[petgraph::prelude::NodeIndex],
+    trailends: &[petgraph::prelude::NodeIndex],
+    graph: &petgraph::Graph<usize, u32>,
+    score_fn: impl Fn(
+        &petgraph::Graph<usize, u32>,
+        &petgraph::prelude::NodeIndex,
+        &petgraph::prelude::NodeIndex,
+    ) -> usize,
+) -> usize {
+    trailheads
+        .iter()
+        .map(|head_idx| {
+            trailends
+                .iter()
+                .map(|end_idx| score_fn(graph, head_idx, end_idx))
+                .sum::<usize>()
+        })
+        .sum()
+}
+
+fn build_graph(
+    matrix: &Grid<i32>,
+) -> (
+    petgraph::Graph<usize, u32, petgraph::Directed>,
+    Vec<petgraph::prelude::NodeIndex>,
+    Vec<petgraph::prelude::NodeIndex>,
+) {
+    let mut graph = DiGraph::new();
+    let mut trailheads = Vec::new();
+    let mut trailends = Vec::new();
+    let mut m_id = 0;
+    let g_ids = matrix.map(|val| {
+        let graph_id = graph.add_node(m_id);
+        m_id += 1;
+        if *val == 0 {
+            trailheads.push(graph_id);
+        } else if *val == 9 {
+            trailends.push(graph_id);
+        }
+        graph_id
+    });
+    for (p, val) in matrix.iter() {
+        for next in matrix.neighbours4(p) {
+            let weight = matrix[next] - val;
+            if weight == 1 {
+                graph.add_edge(g_ids[p], g_ids[next], weight as u32);
+            }
+        }
+    }
+    (graph, trailheads, trailends)
+}
+
+fn parse(input: &str) -> Result<Grid<i32>, ParseError> {
+    Grid::parse(input, "digit", |c| c.to_digit(10).map(|d| d as i32))
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = "89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732";
+        let answer = part1(&parse(input).unwrap());
+        assert_eq!(answer, 36);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732";
+        let answer = part2(&parse(input).unwrap());
+        assert_eq!(answer, 81);
+    }
+}
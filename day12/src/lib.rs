@@ -0,0 +1,333 @@
+use std::collections::{HashSet, VecDeque};
+
+use common::{Answer, ParseError, Solution};
+use grid::{Direction, Grid, Point};
+use petgraph::{
+    unionfind::UnionFind,
+    visit::{Bfs, EdgeRef, NodeIndexable},
+    Graph,
+};
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Grid<char>;
+
+    fn parse(&self, input: &str) -> Result<Grid<char>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, garden: &Grid<char>) -> Answer {
+        part1(garden).into()
+    }
+
+    fn part2(&self, garden: &Grid<char>) -> Answer {
+        part2(garden).into()
+    }
+}
+
+fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse(input, "plant letter", |c| {
+        c.is_ascii_alphabetic().then_some(c)
+    })
+}
+
+fn part1(garden: &Grid<char>) -> usize {
+    let mut graph = Graph::new_undirected();
+
+    let graph_index_table = garden.map(|_| graph.add_node(()));
+    for (p, plot) in garden.iter() {
+        for next in garden.neighbours4(p) {
+            if *plot == garden[next] {
+                graph.update_edge(graph_index_table[p], graph_index_table[next], ());
+            }
+        }
+    }
+    //find each region
+    let mut vertex_sets = UnionFind::new(graph.node_bound());
+    for edge in graph.edge_references() {
+        let (a, b) = (edge.source(), edge.target());
+        // union the two vertices of the edge
+        vertex_sets.union(graph.to_index(a), graph.to_index(b));
+    }
+    let mut roots = vertex_sets.into_labeling();
+    roots.sort_unstable();
+    roots.dedup();
+    //for each region, walk the graph
+    //for each node, each edge is a lack of a fence
+    roots
+        .iter()
+        .map(|idx| {
+            let idx = graph.from_index(*idx);
+            let mut area = 0;
+            let mut perim = 0;
+            let mut bfs = Bfs::new(&graph, idx);
+            while let Some(nx) = bfs.next(&graph) {
+                area += 1;
+                perim += 4 - graph.neighbors(nx).count();
+            }
+            area * perim
+        })
+        .sum()
+}
+
+//can't think of how to reuse the graph method from part1, so start over
+fn part2(garden: &Grid<char>) -> usize {
+    let garden = gen_garden(garden);
+
+    (0..=find_max_region(&garden))
+        .map(|i| {
+            let mut area = 0;
+            let mut walls = 0;
+            //compute area and horizontal walls
+            for line in garden.rows() {
+                let mut found_top_wall = false;
+                let mut found_bottom_wall = false;
+                for plot in line.iter() {
+                    if plot.region != i {
+                        if found_top_wall {
+                            walls += 1;
+                            found_top_wall = false;
+                        }
+                        if found_bottom_wall {
+                            walls += 1;
+                            found_bottom_wall = false;
+                        }
+                        continue;
+                    }
+                    area += 1;
+                    if plot.top_wall {
+                        found_top_wall = true;
+                    } else if found_top_wall {
+                        walls += 1;
+                        found_top_wall = false;
+                    }
+                    if plot.down_wall {
+                        found_bottom_wall = true;
+                    } else if found_bottom_wall {
+                        walls += 1;
+                        found_bottom_wall = false;
+                    }
+                }
+                if found_top_wall {
+                    walls += 1;
+                }
+                if found_bottom_wall {
+                    walls += 1;
+                }
+            }
+            //compute vertical walls
+            for x in 0..garden.width() {
+                let mut found_right_wall = false;
+                let mut found_left_wall = false;
+                for y in 0..garden.height() {
+                    let plot = &garden[Point::new(y, x)];
+                    if plot.region != i {
+                        if found_right_wall {
+                            walls += 1;
+                            found_right_wall = false;
+                        }
+                        if found_left_wall {
+                            walls += 1;
+                            found_left_wall = false;
+                        }
+                        continue;
+                    }
+                    if plot.right_wall {
+                        found_right_wall = true;
+                    } else if found_right_wall {
+                        walls += 1;
+                        found_right_wall = false;
+                    }
+                    if plot.left_wall {
+                        found_left_wall = true;
+                    } else if found_left_wall {
+                        walls += 1;
+                        found_left_wall = false;
+                    }
+                }
+                if found_right_wall {
+                    walls += 1;
+                }
+                if found_left_wall {
+                    walls += 1;
+                }
+            }
+            area * walls
+        })
+        .sum()
+}
+
+fn find_max_region(garden: &Grid<Crop>) -> usize {
+    let mut result = 0;
+    for (_, plot) in garden.iter() {
+        result = result.max(plot.region);
+    }
+    result
+}
+
+fn gen_garden(garden: &Grid<char>) -> Grid<Crop> {
+    let mut garden = garden.map(|c| Crop::new(*c));
+    let mut region_idx = 0;
+    let mut row_hint = 0;
+    let mut visited = HashSet::new();
+    while let Some(start) = find_first_unvisited(&garden, &visited, row_hint) {
+        row_hint = start.y;
+        let mut queue = VecDeque::new();
+        visited.insert(start);
+        queue.push_front(start);
+        while let Some(p) = queue.pop_back() {
+            garden[p].region = region_idx;
+            for dir in Direction::ORTHOGONAL {
+                match garden.step(p, dir) {
+                    Some(next) if garden[p].crop == garden[next].crop => {
+                        if visited.insert(next) {
+                            queue.push_front(next);
+                        }
+                    }
+                    _ => match dir {
+                        Direction::Left => garden[p].left_wall = true,
+                        Direction::Right => garden[p].right_wall = true,
+                        Direction::Up => garden[p].top_wall = true,
+                        _ => garden[p].down_wall = true,
+                    },
+                }
+            }
+        }
+        region_idx += 1;
+    }
+    garden
+}
+
+fn find_first_unvisited(
+    garden: &Grid<Crop>,
+    visited: &HashSet<Point>,
+    row_hint: usize,
+) -> Option<Point> {
+    garden
+        .points()
+        .skip(row_hint * garden.width())
+        .find(|p| !visited.contains(p))
+}
+
+#[derive(Debug)]
+struct Crop {
+    crop: char,
+    top_wall: bool,
+    right_wall: bool,
+    down_wall: bool,
+    left_wall: bool,
+    region: usize,
+}
+
+impl Crop {
+    fn new(crop: char) -> Crop {
+        Crop {
+            crop,
+            top_wall: false,
+            right_wall: false,
+            down_wall: false,
+            left_wall: false,
+            region: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = "RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE
+";
+        let answer = part1(&parse(input).unwrap());
+        assert_eq!(answer, 1930);
+    }
+
+    #[test]
+    fn test_part1_small1() {
+        let input = "AAAA
+BBCD
+BBCC
+EEEC
+";
+        let answer = part1(&parse(input).unwrap());
+        assert_eq!(answer, 140);
+    }
+
+    #[test]
+    fn test_part1_small2() {
+        let input = "OOOOO
+OXOXO
+OOOOO
+OXOXO
+OOOOO
+";
+        let answer = part1(&parse(input).unwrap());
+        assert_eq!(answer, 772);
+    }
+
+    #[test]
+    fn test_part2_small1() {
+        let input = "AAAA
+BBCD
+BBCC
+EEEC
+";
+        let answer = part2(&parse(input).unwrap());
+        assert_eq!(answer, 80);
+    }
+
+    #[test]
+    fn test_part2_small2() {
+        let input = "EEEEE
+EXXXX
+EEEEE
+EXXXX
+EEEEE
+";
+        let answer = part2(&parse(input).unwrap());
+        assert_eq!(answer, 236);
+    }
+
+    #[test]
+    fn test_part2_small3() {
+        let input = "AAAAAA
+AAABBA
+AAABBA
+ABBAAA
+ABBAAA
+AAAAAA
+";
+        let answer = part2(&parse(input).unwrap());
+        assert_eq!(answer, 368);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE
+";
+        let answer = part2(&parse(input).unwrap());
+        assert_eq!(answer, 1206);
+    }
+}
@@ -0,0 +1,297 @@
+use common::{lines, Answer, Cursor, ParseError, Solution};
+
+pub struct Day14 {
+    pub x_size: i32,
+    pub y_size: i32,
+    pub seconds: i32,
+}
+
+impl Default for Day14 {
+    fn default() -> Self {
+        Day14 {
+            x_size: 101,
+            y_size: 103,
+            seconds: 100,
+        }
+    }
+}
+
+impl Solution for Day14 {
+    type Input = Vec<Guard>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Guard>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, guards: &Vec<Guard>) -> Answer {
+        part1(guards, self.x_size, self.y_size, self.seconds).into()
+    }
+
+    fn part2(&self, guards: &Vec<Guard>) -> Answer {
+        self.find_tree(guards).into()
+    }
+}
+
+impl Day14 {
+    pub fn find_tree(&self, guards: &[Guard]) -> i32 {
+        part2(guards, self.x_size, self.y_size)
+    }
+
+    pub fn print_after(&self, guards: &[Guard], seconds: i32) {
+        let space_size = XY {
+            x: self.x_size,
+            y: self.y_size,
+        };
+        let guards: Vec<_> = guards
+            .iter()
+            .cloned()
+            .map(|mut guard| {
+                guard.patrol(seconds);
+                guard.teleport(&space_size);
+                guard
+            })
+            .collect();
+        print_guards(&guards, &space_size);
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<Guard>, ParseError> {
+    lines(input).map(Guard::try_from).collect()
+}
+
+fn part1(guards: &[Guard], x_size: i32, y_size: i32, seconds: i32) -> i32 {
+    let quads = guards.iter().cloned().filter_map(|mut guard| {
+        guard.patrol(seconds);
+        let space_size = XY {
+            x: x_size,
+            y: y_size,
+        };
+        guard.teleport(&space_size);
+        guard.quad(&space_size)
+    });
+    quad_score(quads)
+}
+
+fn part2(guards: &[Guard], x_size: i32, y_size: i32) -> i32 {
+    let space_size = XY {
+        x: x_size,
+        y: y_size,
+    };
+    let mut guards = guards.to_vec();
+    let mut min_score = i32::MAX;
+    let mut min_seconds = 0;
+    // every guard is back where it started after x_size * y_size seconds,
+    // so the picture has to show up within that many
+    for seconds in 1..=x_size * y_size {
+        for guard in &mut guards {
+            guard.patrol(1);
+            guard.teleport(&space_size);
+        }
+        // if the picture is in/near the center,
+        // then we expect a very low quad score
+        let quads = guards.iter().filter_map(|guard| guard.quad(&space_size));
+        let score = quad_score(quads);
+        if score < min_score {
+            min_score = score;
+            min_seconds = seconds;
+        }
+    }
+    min_seconds
+}
+
+fn quad_score<I: Iterator<Item = Quadrant>>(guards: I) -> i32 {
+    let quad_count = guards.fold(QuadCount::new(), |mut count, quad| {
+        match quad {
+            Quadrant::TL => count.tl += 1,
+            Quadrant::TR => count.tr += 1,
+            Quadrant::BL => count.bl += 1,
+            Quadrant::BR => count.br += 1,
+        };
+        count
+    });
+    quad_count.tl * quad_count.tr * quad_count.bl * quad_count.br
+}
+
+fn print_guards(guards: &[Guard], space_size: &XY) {
+    let mut grid = vec![vec![0; space_size.x as usize]; space_size.y as usize];
+    for guard in guards {
+        grid[guard.position.y as usize][guard.position.x as usize] += 1;
+    }
+    for line in &grid {
+        for space in line {
+            if *space == 0 {
+                print!(".");
+            } else {
+                print!("{}", space);
+            }
+        }
+        println!();
+    }
+}
+
+impl TryFrom<Cursor<'_>> for Guard {
+    type Error = ParseError;
+
+    fn try_from(mut line: Cursor<'_>) -> Result<Self, Self::Error> {
+        line.expect("p=")?;
+        let p_x = line.number()?;
+        line.expect(",")?;
+        let p_y = line.number()?;
+        line.expect(" v=")?;
+        let v_x = line.number()?;
+        line.expect(",")?;
+        let v_y = line.number()?;
+        line.end()?;
+        Ok(Guard {
+            position: XY { x: p_x, y: p_y },
+            velocity: XY { x: v_x, y: v_y },
+        })
+    }
+}
+
+impl TryFrom<&str> for Guard {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Guard::try_from(Cursor::new(1, s))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct XY {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Guard {
+    position: XY,
+    velocity: XY,
+}
+
+#[derive(Debug)]
+enum Quadrant {
+    TL,
+    TR,
+    BL,
+    BR,
+}
+
+#[derive(Debug)]
+struct QuadCount {
+    tl: i32,
+    tr: i32,
+    bl: i32,
+    br: i32,
+}
+
+impl QuadCount {
+    fn new() -> QuadCount {
+        QuadCount {
+            tl: 0,
+            tr: 0,
+            bl: 0,
+            br: 0,
+        }
+    }
+}
+
+impl Guard {
+    fn patrol(&mut self, seconds: i32) {
+        self.position.x += self.velocity.x * seconds;
+        self.position.y += self.velocity.y * seconds;
+    }
+    fn teleport(&mut self, space_size: &XY) {
+        let mut new_x = self.position.x % space_size.x;
+        let mut new_y = self.position.y % space_size.y;
+        if new_x < 0 {
+            new_x += space_size.x;
+        }
+        if new_y < 0 {
+            new_y += space_size.y;
+        }
+        self.position.x = new_x;
+        self.position.y = new_y;
+    }
+    fn quad(&self, space_size: &XY) -> Option<Quadrant> {
+        let half_x = space_size.x / 2;
+        let half_y = space_size.y / 2;
+        if self.position.x < half_x {
+            if self.position.y < half_y {
+                Some(Quadrant::TL)
+            } else if self.position.y >= space_size.y - half_y {
+                Some(Quadrant::BL)
+            } else {
+                //middle
+                None
+            }
+        } else if self.position.x >= space_size.x - half_x {
+            if self.position.y < half_y {
+                Some(Quadrant::TR)
+            } else if self.position.y >= space_size.y - half_y {
+                Some(Quadrant::BR)
+            } else {
+                //middle
+                None
+            }
+        } else {
+            //middle
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = "p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3";
+        let answer = part1(&parse(input).unwrap(), 11, 7, 100);
+        assert_eq!(answer, 12);
+    }
+
+    #[test]
+    fn test_part1_small1() {
+        let input = "p=2,4 v=2,-3";
+        let mut guard = Guard::try_from(input).unwrap();
+        guard.patrol(5);
+        guard.teleport(&XY { x: 11, y: 7 });
+        assert_eq!(guard.position, XY { x: 1, y: 3 });
+    }
+
+    // `aoc run` with no --day runs this, so it has to stop on its own
+    #[test]
+    fn test_part2_stops() {
+        let input = "p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1";
+        let day = Day14 {
+            x_size: 11,
+            y_size: 7,
+            seconds: 100,
+        };
+        let answer = day.find_tree(&parse(input).unwrap());
+        assert!((1..=11 * 7).contains(&answer));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let result = parse("p=0,4 v=3,-3\np=6,3 v=-1;-3").err();
+        assert_eq!(result, Some(ParseError::new(2, 11, ";-3", "\",\"")));
+    }
+}
@@ -0,0 +1,329 @@
+use std::ops::{Index, IndexMut};
+
+use common::{char_map, ParseError};
+
+// Coordinates are (y, x) like everywhere else in the repo, so the derived
+// ordering walks the grid row by row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub y: usize,
+    pub x: usize,
+}
+
+impl Point {
+    pub fn new(y: usize, x: usize) -> Point {
+        Point { y, x }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    // (dy, dx) of a single step
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::UpRight => (-1, 1),
+            Direction::Right => (0, 1),
+            Direction::DownRight => (1, 1),
+            Direction::Down => (1, 0),
+            Direction::DownLeft => (1, -1),
+            Direction::Left => (0, -1),
+            Direction::UpLeft => (-1, -1),
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        self.turn_right().turn_right()
+    }
+
+    // 90 degrees clockwise
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::UpRight => Direction::DownRight,
+            Direction::Right => Direction::Down,
+            Direction::DownRight => Direction::DownLeft,
+            Direction::Down => Direction::Left,
+            Direction::DownLeft => Direction::UpLeft,
+            Direction::Left => Direction::Up,
+            Direction::UpLeft => Direction::UpRight,
+        }
+    }
+}
+
+// A rectangular grid stored row by row in one flat buffer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            data: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "grid rows must all be the same width"
+        );
+        Grid {
+            data: rows.into_iter().flatten().collect(),
+            width,
+            height,
+        }
+    }
+
+    // Parses a character map, see `common::char_map` for the error reporting.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        f: impl Fn(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        Ok(Grid::from_rows(char_map(input, expected, f)?))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.y < self.height && p.x < self.width
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        if self.contains(p) {
+            Some(&self.data[p.y * self.width + p.x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        if self.contains(p) {
+            Some(&mut self.data[p.y * self.width + p.x])
+        } else {
+            None
+        }
+    }
+
+    // Moves `p` by (dy, dx), or None if that leaves the grid.
+    pub fn offset(&self, p: Point, dy: isize, dx: isize) -> Option<Point> {
+        let y = p.y.checked_add_signed(dy)?;
+        let x = p.x.checked_add_signed(dx)?;
+        let next = Point { y, x };
+        self.contains(next).then_some(next)
+    }
+
+    pub fn step(&self, p: Point, dir: Direction) -> Option<Point> {
+        let (dy, dx) = dir.offset();
+        self.offset(p, dy, dx)
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point { y, x }))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.data.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.data.chunks(self.width.max(1))
+    }
+
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ORTHOGONAL
+            .into_iter()
+            .filter_map(move |dir| self.step(p, dir))
+    }
+
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |dir| self.step(p, dir))
+    }
+
+    // Every cell from `start` (inclusive) to the edge of the grid in `dir`.
+    pub fn ray(&self, start: Point, dir: Direction) -> Ray<'_, T> {
+        let (dy, dx) = dir.offset();
+        self.ray_by(start, dy, dx)
+    }
+
+    // Like `ray`, but stepping by an arbitrary (dy, dx), e.g. (1, 2) for a
+    // knight's move or (0, 3) to visit every third cell of a row.
+    pub fn ray_by(&self, start: Point, dy: isize, dx: isize) -> Ray<'_, T> {
+        Ray {
+            grid: self,
+            next: self.contains(start).then_some(start),
+            dy,
+            dx,
+        }
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            data: self.data.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    // Turns the grid back into a character map, one line per row.
+    pub fn render(&self, f: impl Fn(&T) -> char) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            out.extend(row.iter().map(&f));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p).expect("point out of bounds")
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        self.get_mut(p).expect("point out of bounds")
+    }
+}
+
+pub struct Ray<'a, T> {
+    grid: &'a Grid<T>,
+    next: Option<Point>,
+    dy: isize,
+    dx: isize,
+}
+
+impl<'a, T> Iterator for Ray<'a, T> {
+    type Item = (Point, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let p = self.next?;
+        // a zero step would never leave the grid
+        self.next = if (self.dy, self.dx) == (0, 0) {
+            None
+        } else {
+            self.grid.offset(p, self.dy, self.dx)
+        };
+        Some((p, &self.grid[p]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    fn letters() -> Grid<char> {
+        Grid::parse("abc\ndef\nghi", "letter", Some).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_render() {
+        let grid = letters();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid[Point::new(1, 2)], 'f');
+        assert_eq!(grid.render(|c| *c), "abc\ndef\nghi\n");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let result = Grid::parse("ab\nc", "letter", Some).err();
+        assert_eq!(result, Some(ParseError::new(2, 2, "", "letter")));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = letters();
+        let corner: Vec<_> = grid.neighbours4(Point::new(0, 0)).collect();
+        assert_eq!(corner, vec![Point::new(0, 1), Point::new(1, 0)]);
+        assert_eq!(grid.neighbours4(Point::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8(Point::new(2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn test_rays() {
+        let grid = letters();
+        let ray: String = grid
+            .ray(Point::new(2, 0), Direction::UpRight)
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(ray, "gec");
+        let ray: String = grid
+            .ray(Point::new(1, 1), Direction::Left)
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(ray, "ed");
+        assert_eq!(grid.ray(Point::new(3, 0), Direction::Up).count(), 0);
+    }
+
+    #[test]
+    fn test_ray_by() {
+        let grid = letters();
+        let ray: Vec<_> = grid.ray_by(Point::new(0, 0), 1, 2).collect();
+        assert_eq!(
+            ray,
+            vec![(Point::new(0, 0), &'a'), (Point::new(1, 2), &'f')]
+        );
+        let ray: Vec<_> = grid.ray_by(Point::new(2, 2), -2, -2).collect();
+        assert_eq!(
+            ray,
+            vec![(Point::new(2, 2), &'i'), (Point::new(0, 0), &'a')]
+        );
+        assert_eq!(grid.ray_by(Point::new(1, 1), 0, 0).count(), 1);
+    }
+}